@@ -0,0 +1,196 @@
+use crate::chunk::ty::ancillary::fctl::FCTL;
+use crate::PNGReader;
+use crate::error::PngError;
+
+/// Size of the sequence number at the start of each fdAT chunk.
+const SEQUENCE_NUMBER_SIZE: usize = 4;
+
+/// A single frame of an animated PNG. Holds a copy of the frame's fcTL chunk, and the frame's
+/// compressed image data.
+pub struct ApngFrame {
+    /// The fcTL chunk that came before the frame data.
+    pub frame_control: FCTL,
+    /// The data of every IDAT chunk (for the default image) or fdAT chunk (for every other frame) of the
+    /// frame, concatenated in order. The sequence number at the start of each fdAT chunk is removed, so
+    /// this is a zlib datastream, just like IDAT data.
+    pub data: Vec<u8>,
+}
+
+impl PNGReader<'_> {
+    /// Groups the image data of an animated PNG into frames. Each fcTL chunk starts a new frame, and the
+    /// IDAT or fdAT chunks that follow it hold the data for that frame. If there is no fcTL chunk before
+    /// the IDAT chunks, the default image is not part of the animation, and the IDAT data is skipped.
+    ///
+    /// Fails if the fcTL and fdAT sequence numbers do not start at 0 and increase by 1, if an fcTL
+    /// chunk is malformed or invalid, or if there is fdAT data before the first fcTL chunk. Returns an
+    /// empty vec for files that are not animated.
+    pub fn get_apng_frames(&self) -> Result<Vec<ApngFrame>, PngError> {
+        let mut frames = vec![];
+        let mut current_frame: Option<ApngFrame> = None;
+        let mut expected_sequence_number = 0;
+
+        for chunk in self {
+            match chunk.get_chunk_type() {
+                "fcTL" => {
+                    let frame_control = *FCTL::from_chunk_refs(&chunk).ok_or_else(|| {
+                        PngError::InvalidData("fcTL chunk is not a valid size".to_string())
+                    })?;
+                    frame_control.validate()?;
+                    Self::check_sequence_number(frame_control.get_sequence_number(), &mut expected_sequence_number)?;
+
+                    frames.extend(current_frame.take());
+                    current_frame = Some(ApngFrame { frame_control, data: vec![] });
+                }
+                // Only the default image can be held in IDAT chunks, so IDAT data only belongs to the
+                // first frame.
+                "IDAT" => {
+                    if let Some(frame) = current_frame.as_mut().filter(|_| frames.is_empty()) {
+                        frame.data.extend_from_slice(chunk.get_chunk_data());
+                    }
+                }
+                "fdAT" => {
+                    let frame = current_frame.as_mut().ok_or_else(|| {
+                        PngError::InvalidData("fdAT chunk does not have an fcTL chunk before it".to_string())
+                    })?;
+                    let data = chunk.get_chunk_data();
+                    if data.len() < SEQUENCE_NUMBER_SIZE {
+                        return Err(PngError::InvalidData(
+                            format!("fdAT chunk is too short to hold a sequence number. length: {}", data.len()),
+                        ));
+                    }
+
+                    let (sequence_number, frame_data) = data.split_at(SEQUENCE_NUMBER_SIZE);
+                    let sequence_number = u32::from_be_bytes(sequence_number.try_into().unwrap());
+                    Self::check_sequence_number(sequence_number, &mut expected_sequence_number)?;
+
+                    frame.data.extend_from_slice(frame_data);
+                }
+                _ => {}
+            }
+        }
+
+        frames.extend(current_frame);
+
+        Ok(frames)
+    }
+    /// Checks that the sequence number is the expected one, and then moves on to the next one.
+    fn check_sequence_number(sequence_number: u32, expected: &mut u32) -> Result<(), PngError> {
+        if sequence_number != *expected {
+            return Err(PngError::InvalidData(
+                format!("Animation chunk is out of order. Expected sequence number: {expected} sequence number: {sequence_number}"),
+            ));
+        }
+
+        *expected += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::PNGBuilder;
+    use crate::chunk::ty::ancillary::actl::ACTL;
+    use crate::chunk::ty::ancillary::fctl::FCTL;
+    use crate::chunk::PNGChunk;
+    use crate::PNGReader;
+
+    fn frame_data(sequence_number: u32, data: &[u8]) -> PNGChunk {
+        let mut frame_data = sequence_number.to_be_bytes().to_vec();
+        frame_data.extend(data);
+
+        PNGChunk::new("fdAT", &frame_data).unwrap()
+    }
+
+    fn build(chunks: Vec<PNGChunk>) -> Vec<u8> {
+        PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 2, 0, 0, 0, 2, 8, 6, 0, 0, 0]).unwrap())
+            .with_chunks(chunks)
+            .build()
+            .expect("Could not build PNG file")
+    }
+
+    #[test]
+    fn get_apng_frames() {
+        let png_file = build(vec![
+            ACTL::new(3, 0).unwrap(),
+            FCTL::new(0, 2, 2, 0, 0, 1, 10, 0, 0).unwrap(),
+            PNGChunk::new("IDAT", &[1, 2]).unwrap(),
+            PNGChunk::new("IDAT", &[3]).unwrap(),
+            FCTL::new(1, 1, 1, 1, 1, 1, 10, 0, 0).unwrap(),
+            frame_data(2, &[4, 5]),
+            frame_data(3, &[6]),
+            FCTL::new(4, 2, 1, 0, 1, 1, 10, 0, 0).unwrap(),
+            frame_data(5, &[7]),
+        ]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let frames = png.get_apng_frames().unwrap();
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].data, [1, 2, 3]);
+        assert_eq!(frames[1].data, [4, 5, 6]);
+        assert_eq!(frames[1].frame_control.get_x_offset(), 1);
+        assert_eq!(frames[2].data, [7]);
+        assert_eq!(frames[2].frame_control.get_sequence_number(), 4);
+    }
+
+    #[test]
+    fn default_image_not_in_animation() {
+        let png_file = build(vec![
+            ACTL::new(1, 0).unwrap(),
+            PNGChunk::new("IDAT", &[1, 2]).unwrap(),
+            FCTL::new(0, 2, 2, 0, 0, 1, 10, 0, 0).unwrap(),
+            frame_data(1, &[3]),
+        ]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let frames = png.get_apng_frames().unwrap();
+
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].data, [3]);
+    }
+
+    #[test]
+    fn not_animated() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(png.get_apng_frames().unwrap().is_empty());
+    }
+
+    #[test]
+    fn out_of_order_frames() {
+        let png_file = build(vec![
+            ACTL::new(2, 0).unwrap(),
+            FCTL::new(0, 2, 2, 0, 0, 1, 10, 0, 0).unwrap(),
+            PNGChunk::new("IDAT", &[1, 2]).unwrap(),
+            FCTL::new(2, 2, 2, 0, 0, 1, 10, 0, 0).unwrap(),
+            frame_data(1, &[3]),
+        ]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(png.get_apng_frames().is_err());
+
+        let png_file = build(vec![
+            ACTL::new(1, 0).unwrap(),
+            PNGChunk::new("IDAT", &[1, 2]).unwrap(),
+            frame_data(0, &[3]),
+        ]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(png.get_apng_frames().is_err());
+    }
+
+    #[cfg(feature = "encode")]
+    #[test]
+    fn encoded_animation_frames() {
+        use crate::encode::FrameSpec;
+
+        let frames = vec![
+            FrameSpec::new(2, 2, vec![255; 16], 1, 10),
+            FrameSpec::new(2, 2, vec![0; 16], 1, 10),
+            FrameSpec::new(2, 2, vec![128; 16], 1, 10),
+        ];
+        let png_file = PNGBuilder::animation(2, 2, frames).unwrap();
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let frames = png.get_apng_frames().unwrap();
+        assert_eq!(frames.len(), 3);
+        assert!(frames.iter().all(|frame| !frame.data.is_empty()));
+    }
+}
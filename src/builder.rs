@@ -1,19 +1,55 @@
 #![allow(unused)]
 
+use crate::chunk::crc;
+use crate::chunk::ordering::validate_chunk_order;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::source::ChunkSource;
+use crate::chunk::ty::ancillary::text::TEXt;
+use crate::chunk::ty::critical::ihdr::{IHDRDetails, IHDR};
+use crate::chunk::ty::validate_known_chunk;
 use crate::chunk::PNGChunk;
-use crate::consts::PNG_SIGNATURE;
+use crate::consts::{CHUNK_CRC_SIZE, CHUNK_HEADER_SIZE, PNG_SIGNATURE, PNG_SIGNATURE_LENGTH};
 use crate::PNGReader;
-use std::io::{Error, ErrorKind};
+use crate::error::PngError;
+use std::io::Write;
+use std::ops::Range;
 
-pub struct PNGBuilder {
-    chunks: Vec<PNGChunk>,
+/// The chunk types that may only appear once in a file. IEND is not listed, since `with_chunk` skips IEND
+/// chunks, and the builder adds its own.
+const SINGLE_INSTANCE_CHUNK_TYPES: [&str; 2] = ["IHDR", "PLTE"];
+
+/// Builds a PNG file out of the provided chunks. Chunks can either be owned `PNGChunk`s or `ChunkRefs`
+/// borrowed from another buffer, in which case the builder is tied to the lifetime of that buffer.
+pub struct PNGBuilder<'a> {
+    chunks: Vec<ChunkSource<'a>>,
+    defer_crcs: bool,
+    auto_iend: bool,
+}
+
+impl Default for PNGBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl PNGBuilder {
+/// Collects chunks into a builder, like `with_chunks`. IEND chunks are skipped.
+impl<'a, C: Into<ChunkSource<'a>>> FromIterator<C> for PNGBuilder<'a> {
+    fn from_iter<I: IntoIterator<Item = C>>(chunks: I) -> Self {
+        PNGBuilder::new().with_chunks(chunks)
+    }
+}
+
+impl<'a> PNGBuilder<'a> {
+    #[must_use]
     pub fn new() -> Self {
-        PNGBuilder { chunks: vec![] }
+        PNGBuilder {
+            chunks: vec![],
+            defer_crcs: false,
+            auto_iend: true,
+        }
     }
-    pub fn with_chunk(mut self, chunk: impl Into<PNGChunk>) -> Self {
+    #[must_use]
+    pub fn with_chunk(mut self, chunk: impl Into<ChunkSource<'a>>) -> Self {
         let chunk = chunk.into();
         // Skip over any chunks of type "IEND" when adding new chunks to the builder
         if chunk.get_chunk_type() != "IEND" {
@@ -22,37 +58,267 @@ impl PNGBuilder {
 
         self
     }
-    pub fn with_png(mut self, png: &PNGReader<'_>) -> Self {
+    /// Adds a chunk that may only appear once in a file, like IHDR, PLTE or gAMA. If a chunk of the same
+    /// type has already been added, it is replaced in place, and any other chunks of that type are
+    /// removed. Otherwise, the chunk is appended like `with_chunk`.
+    #[must_use]
+    pub fn with_unique_chunk(mut self, chunk: impl Into<ChunkSource<'a>>) -> Self {
+        let chunk = chunk.into();
+        let Some(position) = self
+            .chunks
+            .iter()
+            .position(|existing| existing.get_chunk_type() == chunk.get_chunk_type())
+        else {
+            return self.with_chunk(chunk);
+        };
+
+        let chunk_type = chunk.get_chunk_type().to_string();
+        self.chunks[position] = chunk;
+        let mut i = 0;
+        self.chunks.retain(|existing| {
+            let keep = i <= position || existing.get_chunk_type() != chunk_type;
+            i += 1;
+            keep
+        });
+
+        self
+    }
+    /// Like `with_chunk`, but runs the typed validator for the chunk type first, if the chunk type is
+    /// one this crate knows about. Unknown chunk types are added without any extra checks.
+    pub fn with_validated_chunk(self, chunk: PNGChunk) -> Result<Self, PngError> {
+        validate_known_chunk(&chunk.as_chunk_refs())?;
+
+        Ok(self.with_chunk(chunk))
+    }
+    /// Adds a chunk that is already serialized as length, chunk type, chunk data and crc. The buffer is
+    /// checked to hold exactly one chunk with a valid chunk type and crc, and is then stored as-is, without
+    /// copying it or recalculating the crc.
+    pub fn with_raw_chunk(self, bytes: Vec<u8>) -> Result<Self, PngError> {
+        let chunk_refs = ChunkRefs::from_raw(&bytes)?;
+        if !chunk_refs.validate_crc() {
+            return Err(PngError::InvalidData(
+                format!("CRC failed. Chunk type: {}, Chunk crc: {:X}, Calculated crc: {:X}",
+                        chunk_refs.get_chunk_type(),
+                        chunk_refs.get_crc(),
+                        chunk_refs.calculate_crc(),
+                ),
+            ));
+        }
+
+        Ok(self.with_chunk(PNGChunk::from_vec_unchecked(bytes)))
+    }
+    /// Adds a tEXt chunk with the provided keyword and text. Fails if the keyword does not follow the
+    /// spec's keyword restrictions, or if the keyword or text contain characters outside of Latin-1.
+    pub fn with_text(self, keyword: &str, text: &str) -> Result<Self, PngError> {
+        Ok(self.with_chunk(TEXt::new(keyword, text)?))
+    }
+    /// Adds an IHDR chunk built from the provided dimensions and details. Fails if the dimensions or
+    /// details are invalid. Like `with_unique_chunk`, this replaces any IHDR that was already added.
+    /// The IHDR must be the first chunk, so call this before adding any other chunks.
+    pub fn with_ihdr(self, width: i32, height: i32, details: IHDRDetails) -> Result<Self, PngError> {
+        let header = IHDR::new(width, height, details)?;
+
+        Ok(self.with_unique_chunk(header.to_chunk()?))
+    }
+    #[must_use]
+    pub fn with_png(mut self, png: &PNGReader<'a>) -> Self {
         for chunk in png {
             self = self.with_chunk(chunk)
         }
 
         self
     }
-    pub fn with_chunks(mut self, chunks: Vec<impl Into<PNGChunk>>) -> Self {
+    /// Adds each chunk in order, like `with_chunk`. Accepts anything that can be iterated over, like a
+    /// `Vec`, an array or an iterator.
+    #[must_use]
+    pub fn with_chunks(mut self, chunks: impl IntoIterator<Item = impl Into<ChunkSource<'a>>>) -> Self {
         for chunk in chunks {
             self = self.with_chunk(chunk)
         }
 
         self
     }
-    pub fn build(self) -> std::io::Result<Vec<u8>> {
-        let mut png = PNG_SIGNATURE.to_vec();
-        let chunk = self.chunks.first().unwrap();
-        if chunk.get_chunk_type() != "IHDR" {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                "Valid IHDR chunk not provided",
-            ));
+    /// Removes every chunk that has already been added with the provided chunk type.
+    #[must_use]
+    pub fn without_chunks_of_type(self, chunk_type: &str) -> Self {
+        self.without_chunk_where(|chunk| chunk.get_chunk_type() == chunk_type)
+    }
+    /// Removes every chunk that has already been added where `predicate` returns true. Borrowed and
+    /// owned chunks are both passed to the predicate as `ChunkRefs`.
+    #[must_use]
+    pub fn without_chunk_where(self, predicate: impl Fn(&ChunkRefs) -> bool) -> Self {
+        self.retain_chunks(|chunk| !predicate(chunk))
+    }
+    /// Keeps only the chunks that have already been added where `f` returns true, like `Vec::retain`.
+    /// The retained chunks stay in the same order. Borrowed and owned chunks are both passed to `f` as
+    /// `ChunkRefs`.
+    #[must_use]
+    pub fn retain_chunks<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&ChunkRefs) -> bool,
+    {
+        self.chunks.retain(|chunk| f(&chunk.as_chunk_refs()));
+        self
+    }
+    /// Ignores the crc stored in each chunk, and instead calculates every crc in one pass over the output
+    /// at the end of `build`. This is useful when chunk data has been changed after the chunks were
+    /// created. With the `rayon` feature, the crcs are calculated in parallel.
+    #[must_use]
+    pub fn defer_crcs(mut self) -> Self {
+        self.defer_crcs = true;
+        self
+    }
+    /// Sets whether `build` and `write_to` append an IEND chunk after the provided chunks. This is on by
+    /// default. Turning it off is useful when the output is going to be joined with the output of another
+    /// builder. Without the IEND chunk, the IHDR chunk is only required to be first if there is one.
+    #[must_use]
+    pub fn with_auto_iend(mut self, auto_iend: bool) -> Self {
+        self.auto_iend = auto_iend;
+        self
+    }
+    /// Returns the length of the file that `build` would produce, including the signature, every chunk
+    /// and the IEND chunk, if it is added. Nothing is built or validated.
+    pub fn encoded_len(&self) -> usize {
+        let chunks_len: usize = self
+            .chunks
+            .iter()
+            .map(|chunk| CHUNK_HEADER_SIZE + chunk.as_chunk_refs().get_length() as usize + CHUNK_CRC_SIZE)
+            .sum();
+        let iend_len = if self.auto_iend { CHUNK_HEADER_SIZE + CHUNK_CRC_SIZE } else { 0 };
+
+        PNG_SIGNATURE_LENGTH + chunks_len + iend_len
+    }
+    pub fn build(mut self) -> Result<Vec<u8>, PngError> {
+        // With `defer_crcs`, the stored crcs are written as-is, and then every crc is calculated in one
+        // pass over the output, which can run in parallel.
+        let defer_crcs = std::mem::replace(&mut self.defer_crcs, false);
+        let chunk_ranges = if defer_crcs { self.chunk_ranges() } else { vec![] };
+
+        let mut png = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut png)?;
+
+        if defer_crcs {
+            Self::set_crcs(&mut png, &chunk_ranges);
+        }
+
+        Ok(png)
+    }
+    /// Writes the PNG file straight to the provided writer, instead of building it in memory first. Writes
+    /// the signature, each chunk and the IEND chunk, with the same checks as `build`. With `defer_crcs`,
+    /// each crc is calculated as its chunk is written.
+    pub fn write_to<W: Write>(self, writer: &mut W) -> Result<(), PngError> {
+        self.validate_first_chunk()?;
+        writer.write_all(&PNG_SIGNATURE)?;
+
+        for chunk in &self.chunks {
+            chunk.write_to(writer, self.defer_crcs)?;
+        }
+
+        if self.auto_iend {
+            let end_section = PNGChunk::new("IEND", &[])?;
+            writer.write_all(end_section.as_slice())?;
         }
 
-        for chunk in self.chunks {
-            png.extend(chunk.as_slice());
+        Ok(())
+    }
+    /// Like `build`, but first checks that the chunks are in an order the spec allows, using
+    /// `validate_chunk_order`. Fails with the first chunk that is out of order. The chunks are not
+    /// reordered.
+    pub fn build_ordered(self) -> Result<Vec<u8>, PngError> {
+        validate_chunk_order(self.chunks.iter().map(|chunk| chunk.get_chunk_type()))?;
+
+        self.build()
+    }
+    /// Like `build`, but first checks that IHDR and PLTE were each added at most once. This catches chunks
+    /// that were added twice by mistake, like when combining `with_png` and `with_chunk`. Fails with the
+    /// type and index of the duplicated chunk. IEND chunks never need checking, since `with_chunk` skips
+    /// them.
+    pub fn try_build(self) -> Result<Vec<u8>, PngError> {
+        self.validate_single_instance_chunks()?;
+
+        self.build()
+    }
+    /// Builds the PNG file like `build`, and then pads the end of the file with zeros so that its length
+    /// is a multiple of `alignment`. The padding goes after the IEND chunk, where decoders ignore it.
+    /// Fails if `alignment` is 0.
+    pub fn build_aligned(self, alignment: usize) -> Result<Vec<u8>, PngError> {
+        if alignment == 0 {
+            return Err(PngError::InvalidData(
+                "Alignment must be greater than 0".to_string(),
+            ));
         }
 
-        let end_section = PNGChunk::new("IEND", &[])?;
-        png.extend(end_section.as_slice());
+        let mut png = self.build()?;
+        png.resize(png.len().next_multiple_of(alignment), 0);
 
         Ok(png)
     }
+    /// Checks that IHDR and PLTE are each added at most once.
+    fn validate_single_instance_chunks(&self) -> Result<(), PngError> {
+        let mut seen = [false; SINGLE_INSTANCE_CHUNK_TYPES.len()];
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            let chunk_type = chunk.get_chunk_type();
+            if let Some(n) = SINGLE_INSTANCE_CHUNK_TYPES.iter().position(|&single| single == chunk_type) {
+                if seen[n] {
+                    return Err(PngError::InvalidData(
+                        format!("Duplicate {chunk_type} chunk. Chunk #: {i}"),
+                    ));
+                }
+                seen[n] = true;
+            }
+        }
+
+        Ok(())
+    }
+    /// Checks that at least one chunk was added, and that the first chunk is IHDR. Without the IEND chunk,
+    /// this only checks that the IHDR chunk is first if there is one.
+    fn validate_first_chunk(&self) -> Result<(), PngError> {
+        if !self.auto_iend && !self.chunks.iter().any(|chunk| chunk.get_chunk_type() == "IHDR") {
+            return Ok(());
+        }
+
+        let chunk = self.chunks.first().ok_or_else(|| {
+            PngError::InvalidData("No chunks provided. The first chunk must be IHDR".to_string())
+        })?;
+        if chunk.get_chunk_type() != "IHDR" {
+            return Err(PngError::InvalidData(
+                "Valid IHDR chunk not provided".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the range that each chunk will cover in the output of `build`, from the start of the length
+    /// field to the end of the crc.
+    fn chunk_ranges(&self) -> Vec<Range<usize>> {
+        let mut start = PNG_SIGNATURE_LENGTH;
+        self.chunks
+            .iter()
+            .map(|chunk| {
+                let end = start + CHUNK_HEADER_SIZE + chunk.as_chunk_refs().get_length() as usize + CHUNK_CRC_SIZE;
+                let range = start..end;
+                start = end;
+                range
+            })
+            .collect()
+    }
+    /// Calculates and writes the crc for each chunk in the provided ranges of the buffer. Each range
+    /// covers a full chunk, from the start of the length field to the end of the crc.
+    fn set_crcs(png: &mut [u8], chunk_ranges: &[Range<usize>]) {
+        #[cfg(feature = "rayon")]
+        use rayon::prelude::*;
+
+        // The crc covers the chunk type and chunk data, but not the length field.
+        let calculate_crc = |range: &Range<usize>| {
+            crc::crc(&png[range.start + std::mem::size_of::<u32>()..range.end - CHUNK_CRC_SIZE])
+        };
+        #[cfg(feature = "rayon")]
+        let crcs: Vec<u32> = chunk_ranges.par_iter().map(calculate_crc).collect();
+        #[cfg(not(feature = "rayon"))]
+        let crcs: Vec<u32> = chunk_ranges.iter().map(calculate_crc).collect();
+
+        for (range, crc) in chunk_ranges.iter().zip(crcs) {
+            png[range.end - CHUNK_CRC_SIZE..range.end].copy_from_slice(&crc.to_be_bytes());
+        }
+    }
 }
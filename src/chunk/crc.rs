@@ -1,4 +1,4 @@
-use std::fmt::{Debug, Formatter};
+use core::fmt::{Debug, Formatter};
 use bytemuck::AnyBitPattern;
 
 #[repr(C)]
@@ -7,10 +7,10 @@ pub struct ChunkCRC {
     crc: [u8; 4],
 }
 
-const _: () = assert!(std::mem::size_of::<ChunkCRC>() == std::mem::size_of::<u32>());
+const _: () = assert!(core::mem::size_of::<ChunkCRC>() == core::mem::size_of::<u32>());
 
 impl Debug for ChunkCRC {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "ChunkCRC {{ crc: 0x{:08X} }}", self.get_crc())
     }
 }
@@ -35,8 +35,72 @@ impl ChunkCRC {
         self.crc = crc.to_be_bytes()
     }
 }
-pub const fn crc(buffer: &[u8]) -> u32 {
-    update_crc(u32::MAX, buffer) ^ u32::MAX
+/// Calculates the crc of the buffer. With the `fast-crc` feature, this uses `crc32fast`. Otherwise, it is
+/// the same as `const_crc`. This is not a const fn under either configuration, so enabling `fast-crc`
+/// does not change its signature. Use `const_crc` in const contexts.
+pub fn crc(buffer: &[u8]) -> u32 {
+    #[cfg(feature = "fast-crc")]
+    {
+        crc32fast::hash(buffer)
+    }
+    #[cfg(not(feature = "fast-crc"))]
+    const_crc(buffer)
+}
+/// Calculates the crc of the buffer with the lookup table. This is always a const fn, so it can be used
+/// in const contexts, whether or not the `fast-crc` feature is enabled.
+pub const fn const_crc(buffer: &[u8]) -> u32 {
+    let mut crc = Crc::new();
+    crc.update(buffer);
+    crc.finalize()
+}
+/// Calculates the crc of a chunk from its chunk type and chunk data, without needing them to be next to
+/// each other in memory. This is the same value as `crc` over the chunk type followed by the chunk data.
+/// Like `crc`, this uses `crc32fast` with the `fast-crc` feature. Use `const_chunk_crc` in const contexts.
+pub fn chunk_crc(chunk_type: &[u8], chunk_data: &[u8]) -> u32 {
+    #[cfg(feature = "fast-crc")]
+    {
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(chunk_type);
+        hasher.update(chunk_data);
+        hasher.finalize()
+    }
+    #[cfg(not(feature = "fast-crc"))]
+    const_chunk_crc(chunk_type, chunk_data)
+}
+/// Calculates the crc of a chunk from its chunk type and chunk data with the lookup table, like
+/// `const_crc`. This is always a const fn.
+pub const fn const_chunk_crc(chunk_type: &[u8], chunk_data: &[u8]) -> u32 {
+    let mut crc = Crc::new();
+    crc.update(chunk_type);
+    crc.update(chunk_data);
+    crc.finalize()
+}
+/// A running crc, for calculating the crc of data that arrives in pieces. Feeding the pieces to `update`
+/// in order gives the same crc as `crc` over all of the pieces joined together. This always uses the
+/// lookup table, even with the `fast-crc` feature.
+#[derive(Debug, Copy, Clone)]
+pub struct Crc {
+    state: u32,
+}
+
+impl Crc {
+    pub const fn new() -> Self {
+        Crc { state: u32::MAX }
+    }
+    /// Adds `bytes` to the crc.
+    pub const fn update(&mut self, bytes: &[u8]) {
+        self.state = update_crc(self.state, bytes);
+    }
+    /// Gets the crc of all of the bytes that have been added.
+    pub const fn finalize(self) -> u32 {
+        self.state ^ u32::MAX
+    }
+}
+
+impl Default for Crc {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 const fn update_crc(mut crc: u32, buffer: &[u8]) -> u32 {
     const CRC_TABLE: [u32; 256] = make_crc_table();
@@ -73,3 +137,42 @@ const fn make_crc_table() -> [u32; 256] {
 
     table
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::chunk::crc::{chunk_crc, const_chunk_crc, const_crc, crc, Crc};
+
+    #[test]
+    fn crc_backends_match() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        // The crc of the IHDR chunk type and data, as stored in the file.
+        const IHDR_CRC: u32 = 0xBC1F334A;
+
+        for buffer in [&png_file[12..29], &png_file[..], &[], b"IEND"] {
+            assert_eq!(crc(buffer), const_crc(buffer));
+        }
+        assert_eq!(crc(&png_file[12..29]), IHDR_CRC);
+        assert_eq!(chunk_crc(&png_file[12..16], &png_file[16..29]), IHDR_CRC);
+        assert_eq!(const_chunk_crc(&png_file[12..16], &png_file[16..29]), IHDR_CRC);
+
+        const IEND_CRC: u32 = const_crc(b"IEND");
+        assert_eq!(IEND_CRC, 0xAE426082);
+        const SPLIT_IEND_CRC: u32 = const_chunk_crc(b"IE", b"ND");
+        assert_eq!(SPLIT_IEND_CRC, IEND_CRC);
+    }
+
+    #[test]
+    fn incremental_crc() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+
+        for piece_len in [1, 7, 4096, png_file.len()] {
+            let mut running = Crc::new();
+            for piece in png_file.chunks(piece_len) {
+                running.update(piece);
+            }
+            assert_eq!(running.finalize(), crc(&png_file));
+        }
+
+        assert_eq!(Crc::default().finalize(), crc(&[]));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::chunk::ty::ChunkType;
-use std::fmt::{Debug, Formatter};
+use crate::consts::MAX_CHUNK_LENGTH;
+use crate::error::PngError;
+use core::fmt::{Debug, Formatter};
 use bytemuck::AnyBitPattern;
 
 #[repr(C)]
@@ -8,10 +10,10 @@ pub struct ChunkHeader {
     length: [u8; 4],
     chunk_type: ChunkType,
 }
-const _: () = assert!(std::mem::size_of::<ChunkHeader>() == std::mem::size_of::<u32>() * 2);
+const _: () = assert!(core::mem::size_of::<ChunkHeader>() == core::mem::size_of::<u32>() * 2);
 
 impl Debug for ChunkHeader {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "ChunkHeader {{ length: {}, chunk_type: \"{}\" }}",
@@ -22,7 +24,7 @@ impl Debug for ChunkHeader {
 }
 
 impl ChunkHeader {
-    pub fn new(length: u32, chunk_type_str: &str) -> std::io::Result<Self> {
+    pub fn new(length: u32, chunk_type_str: &str) -> Result<Self, PngError> {
         Ok(ChunkHeader {
             length: length.to_be_bytes(),
             chunk_type: ChunkType::new(chunk_type_str)?,
@@ -36,9 +38,9 @@ impl ChunkHeader {
     pub fn get_length(&self) -> u32 {
         u32::from_be_bytes(self.length)
     }
-    #[must_use = "Setting the length will fail if the `length` parameter is greater than 0x80000000"]
+    #[must_use = "Setting the length will fail if the `length` parameter is greater than 0x7FFFFFFF"]
     pub fn set_length(&mut self, length: u32) -> bool {
-        if length >= 0x80000000 {
+        if length > MAX_CHUNK_LENGTH {
             return false;
         }
 
@@ -53,11 +55,16 @@ impl ChunkHeader {
     pub fn get_chunk_type(&self) -> [u8; 4] {
         self.chunk_type.get_chunk_type()
     }
-    pub fn validate_chunk_type(&self) -> std::io::Result<()> {
+    /// Gets the `ChunkType`, for checking the property bits of the chunk type.
+    #[inline(always)]
+    pub fn as_chunk_type(&self) -> &ChunkType {
+        &self.chunk_type
+    }
+    pub fn validate_chunk_type(&self) -> Result<(), PngError> {
         self.chunk_type.validate()
     }
     #[inline(always)]
-    pub fn set_chunk_type(&mut self, chunk_type: &str) -> std::io::Result<()> {
+    pub fn set_chunk_type(&mut self, chunk_type: &str) -> Result<(), PngError> {
         self.chunk_type.set_chunk_type(chunk_type)
     }
 }
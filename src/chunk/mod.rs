@@ -2,46 +2,75 @@ use crate::chunk::crc::ChunkCRC;
 use crate::chunk::header::ChunkHeader;
 use crate::chunk::refs::ChunkRefs;
 use crate::chunk::ty::ChunkType;
-use crate::consts::{CHUNK_CRC_SIZE, CHUNK_HEADER_SIZE};
-use std::io::{Error, ErrorKind};
+use crate::consts::{CHUNK_CRC_SIZE, CHUNK_HEADER_SIZE, MAX_CHUNK_LENGTH};
+use crate::error::PngError;
+use alloc::vec::Vec;
 
 pub mod crc;
 pub mod header;
+pub mod ordering;
 pub mod refs;
+#[cfg(feature = "std")]
+pub mod source;
 mod traits;
 pub mod ty;
 
-/// A wrapper around a vector that contains PNG chunk data. This is just the individual chunk.
+/// A wrapper around a vector that contains PNG chunk data. This is just the individual chunk. Two chunks
+/// are equal if their bytes are equal, including the length, chunk type and crc.
+#[derive(Clone, PartialEq, Eq)]
 pub struct PNGChunk {
     data: Vec<u8>,
 }
 
 #[allow(unused)]
 impl PNGChunk {
-    pub fn new(chunk_type: &str, mut chunk_data: &[u8]) -> std::io::Result<PNGChunk> {
-        let mut data = vec![0; CHUNK_HEADER_SIZE];
+    pub fn new(chunk_type: &str, chunk_data: &[u8]) -> Result<PNGChunk, PngError> {
+        // Check the chunk type and length before allocating anything.
+        ChunkType::validate_chunk_type(chunk_type)?;
+        if chunk_data.len() > MAX_CHUNK_LENGTH as usize {
+            return Err(PngError::ChunkTooLong {
+                offset: None,
+                len: chunk_data.len(),
+            });
+        }
+
+        let mut data = Vec::with_capacity(CHUNK_HEADER_SIZE + chunk_data.len() + CHUNK_CRC_SIZE);
+        data.extend((chunk_data.len() as u32).to_be_bytes());
+        data.extend(chunk_type.as_bytes());
         data.extend(chunk_data);
-        data.resize(data.len() + CHUNK_CRC_SIZE, 0);
+        data.extend([0; CHUNK_CRC_SIZE]);
 
         let mut chunk = PNGChunk { data };
-
-        let header = chunk.as_chunk_header_mut();
-        if !header.set_length(chunk_data.len() as u32) {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                format!(
-                    "Chunk data is too long.\nMax: 0x80000000\nLen: 0x{:08X}",
-                    chunk_data.len()
-                ),
-            ));
-        };
-
-        header.set_chunk_type(chunk_type)?;
-
         chunk.calculate_and_set_crc();
 
         Ok(chunk)
     }
+    /// Copies a single serialized chunk (length, chunk type, chunk data and crc) into a new `PNGChunk`.
+    /// Fails if the buffer is not exactly one chunk long, or if the chunk type is invalid. Like
+    /// `ChunkRefs::from_raw`, the crc is not checked, so use `is_valid_crc` if the data is not trusted.
+    pub fn from_slice(bytes: &[u8]) -> Result<PNGChunk, PngError> {
+        ChunkRefs::from_raw(bytes)?;
+
+        Ok(PNGChunk::from_vec_unchecked(bytes.to_vec()))
+    }
+    /// Wraps a buffer that already holds a full chunk (length, chunk type, chunk data and crc). The buffer
+    /// must have been checked with `ChunkRefs::from_raw`, so that the length matches the buffer.
+    pub(crate) fn from_vec_unchecked(data: Vec<u8>) -> PNGChunk {
+        PNGChunk { data }
+    }
+    /// Calculates the crc that a chunk with the provided chunk type and chunk data would have, without
+    /// building the chunk. Fails for the same chunk types and data lengths that `PNGChunk::new` would.
+    pub fn compute_crc(chunk_type: &str, chunk_data: &[u8]) -> Result<u32, PngError> {
+        ChunkType::validate_chunk_type(chunk_type)?;
+        if chunk_data.len() > MAX_CHUNK_LENGTH as usize {
+            return Err(PngError::ChunkTooLong {
+                offset: None,
+                len: chunk_data.len(),
+            });
+        }
+
+        Ok(crc::chunk_crc(chunk_type.as_bytes(), chunk_data))
+    }
     pub fn as_chunk_refs(&self) -> ChunkRefs<'_> {
         self.into()
     }
@@ -54,7 +83,7 @@ impl PNGChunk {
         self.as_chunk_header().get_length()
     }
     #[inline(always)]
-    #[must_use = "Setting the length will fail if the `length` parameter is greater than 0x80000000"]
+    #[must_use = "Setting the length will fail if the `length` parameter is greater than 0x7FFFFFFF"]
     pub fn set_length(&mut self, len: u32) -> bool {
         self.as_chunk_header_mut().set_length(len)
     }
@@ -63,7 +92,7 @@ impl PNGChunk {
         self.as_chunk_header().get_chunk_type_as_str()
     }
     #[inline(always)]
-    pub fn set_chunk_type(&mut self, chunk_type: &str) -> std::io::Result<()> {
+    pub fn set_chunk_type(&mut self, chunk_type: &str) -> Result<(), PngError> {
         self.as_chunk_header_mut().set_chunk_type(chunk_type)
     }
     #[inline(always)]
@@ -72,7 +101,7 @@ impl PNGChunk {
     }
     #[inline(always)]
     fn as_chunk_header_mut(&mut self) -> &mut ChunkHeader {
-        unsafe { &mut *(self.data.as_ptr() as *mut ChunkHeader) }
+        unsafe { &mut *(self.data.as_mut_ptr() as *mut ChunkHeader) }
     }
     // Chunk Data
     #[inline(always)]
@@ -89,6 +118,25 @@ impl PNGChunk {
         let data_end = data_start + header.get_length() as usize;
         &mut self.data[data_start..data_end]
     }
+    /// Replaces the chunk data, resizing the chunk to fit, and recalculates the crc. Fails if the new data
+    /// is longer than 0x7FFFFFFF bytes, in which case the chunk is left unchanged.
+    pub fn set_chunk_data(&mut self, chunk_data: &[u8]) -> Result<(), PngError> {
+        if chunk_data.len() > MAX_CHUNK_LENGTH as usize {
+            return Err(PngError::ChunkTooLong {
+                offset: None,
+                len: chunk_data.len(),
+            });
+        }
+
+        self.data.truncate(CHUNK_HEADER_SIZE);
+        self.data.extend(chunk_data);
+        self.data.resize(self.data.len() + CHUNK_CRC_SIZE, 0);
+        // The length was checked above.
+        let _ = self.set_length(chunk_data.len() as u32);
+        self.calculate_and_set_crc();
+
+        Ok(())
+    }
     // CRC functions
     #[inline(always)]
     pub fn is_valid_crc(&self) -> bool {
@@ -108,23 +156,23 @@ impl PNGChunk {
     }
     fn as_chunk_crc(&self) -> &ChunkCRC {
         let header = self.as_chunk_header();
-        let data_len = header.get_length() as usize + std::mem::size_of::<ChunkHeader>();
+        let data_len = header.get_length() as usize + core::mem::size_of::<ChunkHeader>();
         let crc_buffer = &self.data[data_len..];
         unsafe { &*(crc_buffer.as_ptr() as *const ChunkCRC) }
     }
     fn as_chunk_crc_mut(&mut self) -> &mut ChunkCRC {
         let header = self.as_chunk_header();
-        let data_len = header.get_length() as usize + std::mem::size_of::<ChunkHeader>();
-        let crc_buffer = &self.data[data_len..];
-        unsafe { &mut *(crc_buffer.as_ptr() as *mut ChunkCRC) }
+        let data_len = header.get_length() as usize + core::mem::size_of::<ChunkHeader>();
+        let crc_buffer = &mut self.data[data_len..];
+        unsafe { &mut *(crc_buffer.as_mut_ptr() as *mut ChunkCRC) }
     }
     fn get_crc_data(&self) -> &[u8] {
         let header = self.as_chunk_header();
 
         unsafe {
-            std::slice::from_raw_parts(
+            core::slice::from_raw_parts(
                 header.get_chunk_type_as_str().as_ptr(),
-                header.get_length() as usize + std::mem::size_of::<ChunkType>(),
+                header.get_length() as usize + core::mem::size_of::<ChunkType>(),
             )
         }
     }
@@ -147,3 +195,64 @@ impl<'a> From<&'a PNGChunk> for ChunkRefs<'a> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::PNGChunk;
+    use crate::error::PngError;
+    use alloc::string::ToString;
+
+    #[test]
+    fn clone() {
+        let mut chunk = PNGChunk::new("tEXt", b"Title\0Ferris").unwrap();
+        let clone = chunk.clone();
+        assert!(clone == chunk);
+
+        chunk.get_chunk_data_mut()[6..].copy_from_slice(b"FERRIS");
+        chunk.calculate_and_set_crc();
+
+        assert!(clone != chunk);
+        assert_eq!(clone.get_chunk_data(), b"Title\0Ferris");
+        assert!(clone.is_valid_crc());
+        assert!(clone == PNGChunk::new("tEXt", b"Title\0Ferris").unwrap());
+    }
+
+    #[test]
+    fn invalid_chunk_type() {
+        let err = PNGChunk::new("tEX", b"Title\0Ferris").err().unwrap();
+        assert!(matches!(err, PngError::InvalidChunkType { .. }));
+        assert!(err.to_string().contains("Chunk type is not 4 bytes long"));
+    }
+
+    #[test]
+    fn from_slice() {
+        let chunk = PNGChunk::new("tEXt", b"Title\0Ferris").unwrap();
+        let parsed = PNGChunk::from_slice(chunk.as_slice()).unwrap();
+        assert!(parsed == chunk);
+        assert!(parsed.is_valid_crc());
+
+        let empty = PNGChunk::new("IEND", &[]).unwrap();
+        assert!(PNGChunk::from_slice(empty.as_slice()).unwrap() == empty);
+
+        let bytes = chunk.as_slice();
+        assert!(PNGChunk::from_slice(&bytes[..bytes.len() - 1]).is_err());
+        assert!(PNGChunk::from_slice(&[bytes, &[0]].concat()).is_err());
+        assert!(PNGChunk::from_slice(&bytes[..7]).is_err());
+    }
+
+    #[test]
+    fn set_chunk_data() {
+        let mut chunk = PNGChunk::new("tEXt", b"Title\0Ferris").unwrap();
+        chunk.set_chunk_data(b"Title\0Ferris the crab").unwrap();
+
+        assert_eq!(chunk.get_length(), 21);
+        assert_eq!(chunk.get_chunk_data(), b"Title\0Ferris the crab");
+        assert_eq!(chunk.as_slice().len(), 8 + 21 + 4);
+        assert!(chunk.is_valid_crc());
+        assert!(chunk == PNGChunk::new("tEXt", b"Title\0Ferris the crab").unwrap());
+
+        chunk.set_chunk_data(&[]).unwrap();
+        assert_eq!(chunk.get_length(), 0);
+        assert!(chunk.is_valid_crc());
+    }
+}
@@ -0,0 +1,107 @@
+use crate::error::PngError;
+use alloc::format;
+use alloc::vec::Vec;
+
+/// A constraint on where a chunk type may appear in a file, relative to the PLTE and IDAT chunks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OrderingRule {
+    /// Must come before PLTE, and before the first IDAT.
+    BeforePlte,
+    /// Must come after PLTE, if there is a PLTE chunk, and before the first IDAT.
+    AfterPlte,
+    /// Must come before the first IDAT.
+    BeforeIdat,
+}
+
+/// The ordering constraints the spec places on chunk types, relative to PLTE and IDAT. Chunk types that
+/// are not in this table can appear anywhere between IHDR and IEND. IHDR, PLTE and IDAT themselves have
+/// their own rules, which are checked by `validate_chunk_order`.
+pub const ORDERING_RULES: &[(&str, OrderingRule)] = &[
+    ("cHRM", OrderingRule::BeforePlte),
+    ("gAMA", OrderingRule::BeforePlte),
+    ("iCCP", OrderingRule::BeforePlte),
+    ("sBIT", OrderingRule::BeforePlte),
+    ("sRGB", OrderingRule::BeforePlte),
+    ("bKGD", OrderingRule::AfterPlte),
+    ("hIST", OrderingRule::AfterPlte),
+    ("tRNS", OrderingRule::AfterPlte),
+    ("pHYs", OrderingRule::BeforeIdat),
+    ("sPLT", OrderingRule::BeforeIdat),
+    ("oFFs", OrderingRule::BeforeIdat),
+    ("acTL", OrderingRule::BeforeIdat),
+];
+
+/// Gets the ordering rule for the provided chunk type, if it has one.
+pub fn get_ordering_rule(chunk_type: &str) -> Option<OrderingRule> {
+    ORDERING_RULES
+        .iter()
+        .find(|(rule_chunk_type, _)| *rule_chunk_type == chunk_type)
+        .map(|&(_, rule)| rule)
+}
+
+/// Checks that the provided sequence of chunk types follows the ordering rules of the spec. IHDR must be
+/// first, PLTE must come before the first IDAT, IDAT chunks must be consecutive, IEND (if present) must
+/// be last, and every chunk type in `ORDERING_RULES` must follow its rule. Returns an error describing
+/// the first chunk that is out of order.
+pub fn validate_chunk_order<'a>(chunk_types: impl IntoIterator<Item = &'a str>) -> Result<(), PngError> {
+    let chunk_types: Vec<&str> = chunk_types.into_iter().collect();
+    let plte_index = chunk_types.iter().position(|&chunk_type| chunk_type == "PLTE");
+    let mut seen_plte = false;
+    let mut seen_idat = false;
+    let mut idat_ended = false;
+
+    for (i, &chunk_type) in chunk_types.iter().enumerate() {
+        let out_of_order = |reason: &str| PngError::InvalidData(
+            format!("Chunk is out of order. Chunk #: {i} Chunk type: {chunk_type}. {reason}"),
+        );
+
+        match chunk_type {
+            "IHDR" if i != 0 => return Err(out_of_order("IHDR must be the first chunk")),
+            "IEND" if i != chunk_types.len() - 1 => return Err(out_of_order("IEND must be the last chunk")),
+            "PLTE" if seen_idat => return Err(out_of_order("PLTE must come before IDAT")),
+            "IDAT" if idat_ended => return Err(out_of_order("IDAT chunks must be consecutive")),
+            _ => {}
+        }
+
+        match get_ordering_rule(chunk_type) {
+            Some(OrderingRule::BeforePlte) if seen_plte || seen_idat => {
+                return Err(out_of_order("Must come before PLTE and IDAT"))
+            }
+            Some(OrderingRule::AfterPlte) if plte_index.is_some_and(|plte_index| i < plte_index) || seen_idat => {
+                return Err(out_of_order("Must come after PLTE and before IDAT"))
+            }
+            Some(OrderingRule::BeforeIdat) if seen_idat => {
+                return Err(out_of_order("Must come before IDAT"))
+            }
+            _ => {}
+        }
+
+        match chunk_type {
+            "PLTE" => seen_plte = true,
+            "IDAT" => seen_idat = true,
+            _ if seen_idat => idat_ended = true,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ordering::validate_chunk_order;
+
+    #[test]
+    fn chunk_order() {
+        validate_chunk_order(["IHDR", "gAMA", "PLTE", "tRNS", "pHYs", "IDAT", "IDAT", "tEXt", "IEND"]).unwrap();
+        validate_chunk_order(["IHDR", "tEXt", "tRNS", "IDAT"]).unwrap();
+
+        assert!(validate_chunk_order(["gAMA", "IHDR", "IDAT"]).is_err());
+        assert!(validate_chunk_order(["IHDR", "IDAT", "PLTE"]).is_err());
+        assert!(validate_chunk_order(["IHDR", "PLTE", "gAMA", "IDAT"]).is_err());
+        assert!(validate_chunk_order(["IHDR", "tRNS", "PLTE", "IDAT"]).is_err());
+        assert!(validate_chunk_order(["IHDR", "IDAT", "pHYs"]).is_err());
+        assert!(validate_chunk_order(["IHDR", "IDAT", "tEXt", "IDAT"]).is_err());
+        assert!(validate_chunk_order(["IHDR", "IDAT", "IEND", "tEXt"]).is_err());
+    }
+}
@@ -1,7 +1,12 @@
+use alloc::borrow::Cow;
 use crate::chunk::crc;
 use crate::chunk::crc::ChunkCRC;
 use crate::chunk::header::ChunkHeader;
 use crate::chunk::ty::ChunkType;
+use crate::consts::{CHUNK_CRC_SIZE, CHUNK_HEADER_SIZE, MAX_CHUNK_LENGTH};
+use crate::error::PngError;
+use alloc::format;
+use alloc::string::String;
 
 /// This is a structure that provides references to existing chunk data in a chunk. These chunks of
 /// data are contiguous, and must be next to each-other, in the current implementation.
@@ -20,14 +25,58 @@ impl<'a> ChunkRefs<'a> {
     }
     /// Gets the `chunk_type` field of the `ChunkHeader`
     #[inline(always)]
-    pub fn get_chunk_type(&self) -> &str {
+    pub fn get_chunk_type(&self) -> &'a str {
         self.header.get_chunk_type_as_str()
     }
+    /// Gets the `ChunkType` of the `ChunkHeader`, for checking the property bits of the chunk type.
+    #[inline(always)]
+    pub fn as_chunk_type(&self) -> &'a ChunkType {
+        self.header.as_chunk_type()
+    }
+    /// Returns true if the chunk can be ignored by a decoder. See `ChunkType::is_ancillary`.
+    #[inline(always)]
+    pub fn is_ancillary(&self) -> bool {
+        self.as_chunk_type().is_ancillary()
+    }
+    /// Returns true if the chunk type is not registered in the spec. See `ChunkType::is_private`.
+    #[inline(always)]
+    pub fn is_private(&self) -> bool {
+        self.as_chunk_type().is_private()
+    }
+    /// Returns true if the reserved bit is set. See `ChunkType::is_reserved`.
+    #[inline(always)]
+    pub fn is_reserved(&self) -> bool {
+        self.as_chunk_type().is_reserved()
+    }
+    /// Returns true if the chunk may be copied by editors that do not recognize it. See
+    /// `ChunkType::is_safe_to_copy`.
+    #[inline(always)]
+    pub fn is_safe_to_copy(&self) -> bool {
+        self.as_chunk_type().is_safe_to_copy()
+    }
+    /// Gets a reference to the `ChunkHeader` of the chunk, for callers that want the raw header.
+    #[inline(always)]
+    pub fn get_header(&self) -> &'a ChunkHeader {
+        self.header
+    }
+    /// Gets a reference to the `ChunkCRC` of the chunk
+    #[inline(always)]
+    pub fn as_chunk_crc(&self) -> &'a ChunkCRC {
+        self.crc
+    }
     /// Gets the data in the chunk as a slice
     #[inline(always)]
-    pub fn get_chunk_data(&self) -> &[u8] {
+    pub fn get_chunk_data(&self) -> &'a [u8] {
         self.chunk_data
     }
+    /// Gets the data in the chunk as a string, replacing any invalid UTF-8 with the replacement character.
+    /// This is meant for quickly inspecting chunks that hold text, like JSON or XML in a private chunk.
+    /// It does not know anything about the structure of the chunk, so use the typed chunk views, like
+    /// `TEXt` or `ITXt`, to actually parse chunk data.
+    #[inline(always)]
+    pub fn data_as_string_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.chunk_data)
+    }
     /// Validates the chunks CRC
     #[inline(always)]
     pub fn validate_crc(&self) -> bool {
@@ -43,40 +92,224 @@ impl<'a> ChunkRefs<'a> {
     pub fn get_crc(&self) -> u32 {
         self.crc.get_crc()
     }
-    /// Gets the entire chunk as a slice. This may not be here long, as it requires the references to
-    /// be contiguous.
+    /// Gets a `ChunkRefsJson` summary of the chunk, which can be serialized with serde. The chunk data
+    /// is not included.
+    #[cfg(feature = "serde")]
+    pub fn to_json_value(&self) -> ChunkRefsJson<'a> {
+        let chunk_type = self.as_chunk_type();
+        ChunkRefsJson {
+            chunk_type: self.get_chunk_type(),
+            length: self.get_length(),
+            crc: format!("{:08X}", self.get_crc()),
+            crc_valid: self.validate_crc(),
+            ancillary: chunk_type.is_ancillary(),
+            private: chunk_type.is_private(),
+            reserved: chunk_type.is_reserved(),
+            safe_to_copy: chunk_type.is_safe_to_copy(),
+        }
+    }
+    /// Gets the entire chunk as a slice, from the length field through the crc. This relies on the
+    /// references being contiguous, which `ChunkRefs::new` checks.
     #[inline(always)]
-    #[allow(unused)]
-    fn get_chunk_as_slice(&self) -> &[u8] {
+    pub fn get_chunk_as_slice(&self) -> &'a [u8] {
+        debug_assert!(self.is_contiguous(), "ChunkRefs references are not contiguous");
         unsafe {
-            std::slice::from_raw_parts(
+            core::slice::from_raw_parts(
                 self.header.get_pointer(),
                 self.header.get_length() as usize
-                    + std::mem::size_of::<ChunkHeader>()
-                    + std::mem::size_of::<ChunkCRC>(),
+                    + core::mem::size_of::<ChunkHeader>()
+                    + core::mem::size_of::<ChunkCRC>(),
             )
         }
     }
+    /// Checks that the chunk data starts right after the header, that the crc starts right after the
+    /// chunk data, and that the chunk data is as long as the length in the header.
+    fn is_contiguous(&self) -> bool {
+        let header_end = self.header.get_pointer() as usize + core::mem::size_of::<ChunkHeader>();
+        let data_start = self.chunk_data.as_ptr() as usize;
+        let crc_start = self.crc as *const ChunkCRC as usize;
+
+        header_end == data_start
+            && data_start + self.chunk_data.len() == crc_start
+            && self.chunk_data.len() == self.header.get_length() as usize
+    }
     /// Gets the data for the chunks CRC calculation. This is the chunk type + the chunk data.
     #[inline(always)]
     fn get_crc_data(&self) -> &[u8] {
         unsafe {
-            std::slice::from_raw_parts(
+            core::slice::from_raw_parts(
                 self.header.get_chunk_type_as_str().as_ptr(),
-                self.header.get_length() as usize + std::mem::size_of::<ChunkType>(),
+                self.header.get_length() as usize + core::mem::size_of::<ChunkType>(),
             )
         }
     }
 }
 
+/// Two chunks are equal if they have the same chunk type, length, chunk data and crc. The chunks can be
+/// in different buffers.
+impl PartialEq for ChunkRefs<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_chunk_type() == other.get_chunk_type()
+            && self.get_length() == other.get_length()
+            && self.get_chunk_data() == other.get_chunk_data()
+            && self.get_crc() == other.get_crc()
+    }
+}
+impl Eq for ChunkRefs<'_> {}
+
 impl<'a> ChunkRefs<'a> {
     /// Creates a new ChunkRefs object with the provided references to the individual chunk data.
-    /// These references should be in order and contiguous.
+    /// These references must be in order and contiguous, in the same buffer, since the crc calculation
+    /// and `get_chunk_as_slice` read across them.
+    ///
+    /// # Panics
+    /// Panics if the references are not contiguous, or if the chunk data is not as long as the length in
+    /// the header.
     pub fn new(header: &'a ChunkHeader, chunk_data: &'a [u8], crc: &'a ChunkCRC) -> Self {
-        ChunkRefs {
+        let chunk_refs = ChunkRefs {
             header,
             chunk_data,
             crc,
+        };
+        assert!(chunk_refs.is_contiguous(), "ChunkRefs references are not contiguous");
+
+        chunk_refs
+    }
+    /// Creates a new ChunkRefs object that references the header, data and crc of a single full chunk
+    /// (length, chunk type, chunk data and crc) in the provided buffer, without copying anything. Fails
+    /// if the buffer is truncated, if it has bytes left over after the chunk, if the chunk type is
+    /// invalid, or if the length is over `MAX_CHUNK_LENGTH`. The crc is not checked.
+    pub fn from_raw(buffer: &'a [u8]) -> Result<Self, PngError> {
+        let header: &ChunkHeader = buffer
+            .get(..CHUNK_HEADER_SIZE)
+            .and_then(|bytes| bytemuck::try_from_bytes(bytes).ok())
+            .ok_or(PngError::TruncatedBuffer {
+                offset: 0,
+                expected: CHUNK_HEADER_SIZE,
+                got: buffer.len(),
+            })?;
+        header.validate_chunk_type()?;
+        if header.get_length() > MAX_CHUNK_LENGTH {
+            return Err(PngError::ChunkTooLong {
+                offset: None,
+                len: header.get_length() as usize,
+            });
         }
+
+        let chunk_len = CHUNK_HEADER_SIZE + header.get_length() as usize + CHUNK_CRC_SIZE;
+        if buffer.len() < chunk_len {
+            return Err(PngError::TruncatedBuffer {
+                offset: 0,
+                expected: chunk_len,
+                got: buffer.len(),
+            });
+        }
+        if buffer.len() > chunk_len {
+            return Err(PngError::InvalidData(
+                format!("Buffer contains more than one chunk. Chunk type: {} Expected len: {chunk_len} Buffer len: {}",
+                        header.get_chunk_type_as_str(),
+                        buffer.len(),
+                ),
+            ));
+        }
+
+        let (chunk_data, crc) = buffer[CHUNK_HEADER_SIZE..].split_at(header.get_length() as usize);
+        let crc = bytemuck::from_bytes::<ChunkCRC>(crc);
+
+        Ok(ChunkRefs::new(header, chunk_data, crc))
+    }
+}
+
+/// A serializable summary of a chunk, made by `ChunkRefs::to_json_value`. `ChunkRefs` only holds
+/// references into the file, so this copies out the fields that are useful for inspecting a file.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ChunkRefsJson<'a> {
+    /// The 4 letter chunk type
+    pub chunk_type: &'a str,
+    /// The length of the chunk data
+    pub length: u32,
+    /// The crc stored in the chunk, as an 8 digit hex string
+    pub crc: String,
+    /// Whether the stored crc matches the crc calculated from the chunk type and chunk data
+    pub crc_valid: bool,
+    pub ancillary: bool,
+    pub private: bool,
+    pub reserved: bool,
+    pub safe_to_copy: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::refs::ChunkRefs;
+    use crate::chunk::PNGChunk;
+    #[cfg(feature = "std")]
+    use crate::PNGReader;
+
+    #[test]
+    fn property_bits() {
+        let chunk = PNGChunk::new("tEXt", b"Title\0Ferris").unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(refs.is_ancillary());
+        assert!(!refs.is_private());
+        assert!(!refs.is_reserved());
+        assert!(refs.is_safe_to_copy());
+
+        let chunk = PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(!refs.is_ancillary());
+        assert!(!refs.is_safe_to_copy());
+
+        let chunk = PNGChunk::new("prvt", &[]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(refs.is_ancillary());
+        assert!(refs.is_private());
+        assert!(refs.is_reserved());
+        assert!(refs.is_safe_to_copy());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn get_chunk_as_slice() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        for (offset, chunk) in png.chunks_with_offsets() {
+            let slice = chunk.get_chunk_as_slice();
+            assert_eq!(slice.len(), 4 + 4 + chunk.get_length() as usize + 4);
+            assert_eq!(slice, &png_file[offset..offset + slice.len()]);
+        }
+
+        let chunk = PNGChunk::new("tEXt", b"Title\0Ferris").unwrap();
+        assert_eq!(chunk.as_chunk_refs().get_chunk_as_slice(), chunk.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "ChunkRefs references are not contiguous")]
+    fn new_not_contiguous() {
+        let first = PNGChunk::new("tEXt", b"Title\0Ferris").unwrap();
+        let second = PNGChunk::new("tEXt", b"Author\0Ferris").unwrap();
+        let (first, second) = (first.as_chunk_refs(), second.as_chunk_refs());
+
+        ChunkRefs::new(first.header, second.chunk_data, first.crc);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "std"))]
+    fn to_json_value() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let chunks: Vec<_> = png.into_iter().map(|chunk| chunk.to_json_value()).collect();
+        let json = serde_json::to_value(&chunks).expect("Could not serialize chunks");
+
+        let ihdr = &json[0];
+        assert_eq!(ihdr["chunk_type"], "IHDR");
+        assert_eq!(ihdr["length"], 13);
+        assert_eq!(ihdr["crc"], "BC1F334A");
+        assert_eq!(ihdr["crc_valid"], true);
+        assert_eq!(ihdr["ancillary"], false);
+        assert_eq!(json[1]["chunk_type"], "sRGB");
+        assert_eq!(json[1]["ancillary"], true);
     }
 }
@@ -0,0 +1,63 @@
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use crate::error::PngError;
+use std::io::Write;
+
+/// A chunk that the `PNGBuilder` can write out. Borrowed chunks reference data that already exists in
+/// another buffer, like a `PNGReader`, and are written out as-is without copying them into an owned
+/// chunk first. Owned chunks are `PNGChunk`s that were created or modified by the user.
+pub enum ChunkSource<'a> {
+    Borrowed(ChunkRefs<'a>),
+    Owned(PNGChunk),
+}
+
+impl ChunkSource<'_> {
+    /// Returns references to the chunk data, regardless of whether the chunk is borrowed or owned.
+    pub fn as_chunk_refs(&self) -> ChunkRefs<'_> {
+        match self {
+            ChunkSource::Borrowed(chunk_refs) => *chunk_refs,
+            ChunkSource::Owned(chunk) => chunk.as_chunk_refs(),
+        }
+    }
+    /// Gets the `chunk_type` field of the chunk header
+    #[inline(always)]
+    pub fn get_chunk_type(&self) -> &str {
+        match self {
+            ChunkSource::Borrowed(chunk_refs) => chunk_refs.get_chunk_type(),
+            ChunkSource::Owned(chunk) => chunk.get_chunk_type(),
+        }
+    }
+    /// Writes the full chunk (length, chunk type, chunk data and crc) to the provided writer. Borrowed
+    /// chunks are written with the crc that is stored in the source buffer. If `recalculate_crc` is true,
+    /// the crc is calculated from the chunk type and chunk data instead of using the stored crc.
+    pub fn write_to<W: Write>(&self, writer: &mut W, recalculate_crc: bool) -> Result<(), PngError> {
+        match self {
+            ChunkSource::Owned(chunk) if !recalculate_crc => writer.write_all(chunk.as_slice())?,
+            _ => {
+                let chunk_refs = self.as_chunk_refs();
+                let crc = if recalculate_crc {
+                    chunk_refs.calculate_crc()
+                } else {
+                    chunk_refs.get_crc()
+                };
+                writer.write_all(&chunk_refs.get_length().to_be_bytes())?;
+                writer.write_all(chunk_refs.get_chunk_type().as_bytes())?;
+                writer.write_all(chunk_refs.get_chunk_data())?;
+                writer.write_all(&crc.to_be_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> From<ChunkRefs<'a>> for ChunkSource<'a> {
+    fn from(chunk_refs: ChunkRefs<'a>) -> Self {
+        ChunkSource::Borrowed(chunk_refs)
+    }
+}
+impl From<PNGChunk> for ChunkSource<'_> {
+    fn from(chunk: PNGChunk) -> Self {
+        ChunkSource::Owned(chunk)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use bytemuck::AnyBitPattern;
+
+/// The acTL chunk is part of the APNG extension. It declares that the file is an animated PNG, and
+/// gives the number of frames and the number of times the animation should play. It must appear
+/// before the first IDAT chunk.
+#[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct ACTL {
+    /// Number of frames. 4-byte unsigned integer. Must be at least 1.
+    num_frames: [u8; 4],
+    /// Number of times to loop the animation. 4-byte unsigned integer. 0 means loop forever.
+    num_plays: [u8; 4],
+}
+
+const ACTL_SIZE: usize = 8;
+const _: () = assert!(core::mem::size_of::<ACTL>() == ACTL_SIZE);
+
+impl ACTL {
+    /// Checks that the animation has at least 1 frame.
+    pub fn validate(&self) -> Result<(), PngError> {
+        if self.get_num_frames() == 0 {
+            return Err(PngError::InvalidData(
+                "Invalid number of frames. An animation must have at least 1 frame.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the number of frames in the animation.
+    #[inline(always)]
+    pub fn get_num_frames(&self) -> u32 {
+        u32::from_be_bytes(self.num_frames)
+    }
+    /// Gets the number of times to play the animation. 0 means the animation loops forever.
+    #[inline(always)]
+    pub fn get_num_plays(&self) -> u32 {
+        u32::from_be_bytes(self.num_plays)
+    }
+}
+
+// Associated functions
+impl ACTL {
+    /// Creates a new acTL `PNGChunk` with the provided number of frames and plays. Fails if the number of
+    /// frames is 0.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(num_frames: u32, num_plays: u32) -> Result<PNGChunk, PngError> {
+        let animation_control = ACTL {
+            num_frames: num_frames.to_be_bytes(),
+            num_plays: num_plays.to_be_bytes(),
+        };
+        animation_control.validate()?;
+
+        let mut data = Vec::with_capacity(ACTL_SIZE);
+        data.extend(animation_control.num_frames);
+        data.extend(animation_control.num_plays);
+
+        PNGChunk::new("acTL", &data)
+    }
+    /// Provides a reference to a ACTL provided the chunk_type matches "acTL" and the size of the data
+    /// matches the size of ACTL.
+    pub fn from_chunk_refs<'a>(chunk_refs: &'a ChunkRefs<'a>) -> Option<&'a ACTL> {
+        if chunk_refs.get_chunk_type() != "acTL" {
+            return None;
+        }
+
+        // Checks the size and alignment, instead of casting the pointer.
+        bytemuck::try_from_bytes(chunk_refs.get_chunk_data()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::builder::PNGBuilder;
+    use crate::chunk::ty::ancillary::actl::ACTL;
+    use crate::chunk::PNGChunk;
+    #[cfg(feature = "std")]
+    use crate::PNGReader;
+
+    #[cfg(feature = "std")]
+    fn ihdr() -> PNGChunk {
+        PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]).unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_animation_control() {
+        let png_file = PNGBuilder::new()
+            .with_chunk(ihdr())
+            .with_chunk(ACTL::new(3, 0).unwrap())
+            .with_chunk(PNGChunk::new("IDAT", &[]).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(png.is_apng());
+
+        let actl_chunk = png.get_chunk_of_type("acTL").unwrap();
+        let animation_control = ACTL::from_chunk_refs(&actl_chunk).unwrap();
+        animation_control.validate().unwrap();
+
+        assert_eq!(animation_control.get_num_frames(), 3);
+        assert_eq!(animation_control.get_num_plays(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn not_apng() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(!png.is_apng());
+
+        // acTL after the image data does not count.
+        let png_file = PNGBuilder::new()
+            .with_chunk(ihdr())
+            .with_chunk(PNGChunk::new("IDAT", &[]).unwrap())
+            .with_chunk(ACTL::new(1, 0).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(!png.is_apng());
+    }
+
+    #[test]
+    fn invalid_animation_control() {
+        assert!(ACTL::new(0, 0).is_err());
+
+        let chunk = PNGChunk::new("acTL", &[0, 0, 0, 1]).unwrap();
+        assert!(ACTL::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::chunk::refs::ChunkRefs;
+
+/// The bKGD chunk specifies a default background color to present the image against. The layout of the
+/// chunk depends on the color type in the IHDR chunk, so a view has to be created with the color type.
+///
+/// | Color type | Contents                        |
+/// |------------|---------------------------------|
+/// | 3          | 1-byte palette index            |
+/// | 0, 4       | 2-byte gray level               |
+/// | 2, 6       | 2-byte red, green and blue each |
+#[derive(Debug, Copy, Clone)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct BKGD<'a> {
+    /// The color type from the IHDR chunk.
+    color_type: u8,
+    /// The chunk data, already checked against the size for the color type.
+    data: &'a [u8],
+}
+
+impl BKGD<'_> {
+    /// Gets the color type this background was read with.
+    #[inline(always)]
+    pub fn get_color_type(&self) -> u8 {
+        self.color_type
+    }
+    /// Gets the palette index of the background color. Returns `None` if the color type is not 3.
+    pub fn as_palette_index(&self) -> Option<u8> {
+        match self.color_type {
+            3 => Some(self.data[0]),
+            _ => None,
+        }
+    }
+    /// Gets the gray level of the background color. Returns `None` if the color type is not 0 or 4.
+    pub fn as_gray(&self) -> Option<u16> {
+        match self.color_type {
+            0 | 4 => Some(self.get_sample(0)),
+            _ => None,
+        }
+    }
+    /// Gets the red, green and blue samples of the background color. Returns `None` if the color type is
+    /// not 2 or 6.
+    pub fn as_rgb(&self) -> Option<[u16; 3]> {
+        match self.color_type {
+            2 | 6 => Some([self.get_sample(0), self.get_sample(1), self.get_sample(2)]),
+            _ => None,
+        }
+    }
+    /// Gets the 2-byte sample at the provided sample index.
+    #[inline(always)]
+    fn get_sample(&self, index: usize) -> u16 {
+        u16::from_be_bytes([self.data[index * 2], self.data[index * 2 + 1]])
+    }
+}
+
+// Associated functions
+impl<'a> BKGD<'a> {
+    /// Provides a view over a bKGD chunk, provided the chunk_type matches "bKGD" and the size of the data
+    /// matches the size the color type requires. Returns `None` for an invalid color type.
+    pub fn from_chunk_refs(chunk_refs: &ChunkRefs<'a>, color_type: u8) -> Option<BKGD<'a>> {
+        if chunk_refs.get_chunk_type() != "bKGD" {
+            return None;
+        }
+
+        let data = chunk_refs.get_chunk_data();
+        if data.len() != Self::get_data_size(color_type)? {
+            return None;
+        }
+
+        Some(BKGD { color_type, data })
+    }
+    /// Gets the size of the bKGD data for the provided color type. Returns `None` for an invalid color
+    /// type.
+    pub fn get_data_size(color_type: u8) -> Option<usize> {
+        match color_type {
+            3 => Some(1),
+            0 | 4 => Some(2),
+            2 | 6 => Some(6),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::bkgd::BKGD;
+    use crate::chunk::PNGChunk;
+
+    #[test]
+    fn truecolor_background() {
+        let chunk = PNGChunk::new("bKGD", &[0, 255, 0x12, 0x34, 0, 0]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let background = BKGD::from_chunk_refs(&refs, 6).unwrap();
+
+        assert_eq!(background.as_rgb(), Some([255, 0x1234, 0]));
+        assert_eq!(background.as_gray(), None);
+        assert_eq!(background.as_palette_index(), None);
+
+        assert!(BKGD::from_chunk_refs(&refs, 3).is_none());
+        assert!(BKGD::from_chunk_refs(&refs, 0).is_none());
+    }
+
+    #[test]
+    fn palette_background() {
+        let chunk = PNGChunk::new("bKGD", &[7]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let background = BKGD::from_chunk_refs(&refs, 3).unwrap();
+
+        assert_eq!(background.as_palette_index(), Some(7));
+        assert_eq!(background.as_rgb(), None);
+        assert_eq!(background.as_gray(), None);
+
+        assert!(BKGD::from_chunk_refs(&refs, 2).is_none());
+        assert!(BKGD::from_chunk_refs(&refs, 5).is_none());
+    }
+
+    #[test]
+    fn gray_background() {
+        let chunk = PNGChunk::new("bKGD", &[0x80, 0x00]).unwrap();
+        let refs = chunk.as_chunk_refs();
+
+        assert_eq!(BKGD::from_chunk_refs(&refs, 4).unwrap().as_gray(), Some(0x8000));
+    }
+}
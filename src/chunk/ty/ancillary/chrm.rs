@@ -0,0 +1,170 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use alloc::format;
+use alloc::vec::Vec;
+use bytemuck::AnyBitPattern;
+
+/// The cHRM chunk specifies the 1931 CIE x,y chromaticities of the red, green, and blue display
+/// primaries used in the image, and the referenced white point. Each value is stored as a 4-byte
+/// unsigned integer, representing the x or y value times 100000. For example, a value of 0.3127 would
+/// be stored as 31270.
+#[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct CHRM {
+    /// White point x times 100000.
+    white_point_x: [u8; 4],
+    /// White point y times 100000.
+    white_point_y: [u8; 4],
+    /// Red x times 100000.
+    red_x: [u8; 4],
+    /// Red y times 100000.
+    red_y: [u8; 4],
+    /// Green x times 100000.
+    green_x: [u8; 4],
+    /// Green y times 100000.
+    green_y: [u8; 4],
+    /// Blue x times 100000.
+    blue_x: [u8; 4],
+    /// Blue y times 100000.
+    blue_y: [u8; 4],
+}
+
+const CHRM_SIZE: usize = 32;
+const _: () = assert!(core::mem::size_of::<CHRM>() == CHRM_SIZE);
+/// The chromaticities are stored as integers, scaled by this value.
+const CHROMATICITY_SCALE: f64 = 100000.0;
+
+impl CHRM {
+    /// Validates that each chromaticity is in the range 0 to 1, that is, that each stored value is at
+    /// most 100000.
+    pub fn validate(&self) -> Result<(), PngError> {
+        let values = [
+            self.white_point_x, self.white_point_y,
+            self.red_x, self.red_y,
+            self.green_x, self.green_y,
+            self.blue_x, self.blue_y,
+        ];
+        for value in values {
+            let value = u32::from_be_bytes(value);
+            if value > CHROMATICITY_SCALE as u32 {
+                return Err(PngError::InvalidData(
+                    format!("Chromaticity is out of range. Chromaticity times 100000 must be at most 100000. value: {value}"),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+    /// Gets the x,y chromaticity of the white point.
+    #[inline(always)]
+    pub fn get_white_point(&self) -> (f64, f64) {
+        (Self::unscale(self.white_point_x), Self::unscale(self.white_point_y))
+    }
+    /// Gets the x,y chromaticity of the red primary.
+    #[inline(always)]
+    pub fn get_red(&self) -> (f64, f64) {
+        (Self::unscale(self.red_x), Self::unscale(self.red_y))
+    }
+    /// Gets the x,y chromaticity of the green primary.
+    #[inline(always)]
+    pub fn get_green(&self) -> (f64, f64) {
+        (Self::unscale(self.green_x), Self::unscale(self.green_y))
+    }
+    /// Gets the x,y chromaticity of the blue primary.
+    #[inline(always)]
+    pub fn get_blue(&self) -> (f64, f64) {
+        (Self::unscale(self.blue_x), Self::unscale(self.blue_y))
+    }
+}
+
+// Associated functions
+impl CHRM {
+    /// Creates a new cHRM `PNGChunk` for the provided x,y chromaticities. Each value is multiplied by
+    /// 100000 and rounded. Fails if any of the results do not fit in a u32.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        white_point: (f64, f64),
+        red: (f64, f64),
+        green: (f64, f64),
+        blue: (f64, f64),
+    ) -> Result<PNGChunk, PngError> {
+        let mut data = Vec::with_capacity(CHRM_SIZE);
+        for (x, y) in [white_point, red, green, blue] {
+            data.extend(Self::scale(x)?);
+            data.extend(Self::scale(y)?);
+        }
+
+        PNGChunk::new("cHRM", &data)
+    }
+    /// Provides a reference to a CHRM provided the chunk_type matches "cHRM" and the size of the data
+    /// matches the size of CHRM.
+    pub fn from_chunk_refs<'a>(chunk_refs: &'a ChunkRefs<'a>) -> Option<&'a CHRM> {
+        if chunk_refs.get_chunk_type() != "cHRM" {
+            return None;
+        }
+
+        // Checks the size and alignment, instead of casting the pointer.
+        bytemuck::try_from_bytes(chunk_refs.get_chunk_data()).ok()
+    }
+    /// Converts a stored chromaticity value to its real value.
+    #[inline(always)]
+    fn unscale(value: [u8; 4]) -> f64 {
+        u32::from_be_bytes(value) as f64 / CHROMATICITY_SCALE
+    }
+    /// Converts a chromaticity value to its stored value.
+    fn scale(value: f64) -> Result<[u8; 4], PngError> {
+        let scaled = value * CHROMATICITY_SCALE;
+        if !(0.0..=u32::MAX as f64).contains(&scaled) {
+            return Err(PngError::InvalidData(
+                format!("Chromaticity is out of range. Chromaticity times 100000 must fit in a u32. chromaticity: {value}"),
+            ));
+        }
+
+        // `f64::round` is not in core. The value is not negative, so adding 0.5 and truncating rounds the
+        // same way.
+        Ok(((scaled + 0.5) as u32).to_be_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::chrm::CHRM;
+    use crate::chunk::PNGChunk;
+
+    #[test]
+    fn chromaticity_round_trip() {
+        // sRGB primaries with the D65 white point
+        let chunk = CHRM::new((0.3127, 0.3290), (0.64, 0.33), (0.30, 0.60), (0.15, 0.06)).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let chromaticity = CHRM::from_chunk_refs(&refs).unwrap();
+
+        assert_eq!(chromaticity.get_white_point(), (0.3127, 0.329));
+        assert_eq!(chromaticity.get_red(), (0.64, 0.33));
+        assert_eq!(chromaticity.get_green(), (0.3, 0.6));
+        assert_eq!(chromaticity.get_blue(), (0.15, 0.06));
+        assert_eq!(&refs.get_chunk_data()[..8], &[0, 0, 0x7A, 0x26, 0, 0, 0x80, 0x84]);
+    }
+
+    #[test]
+    fn invalid_chromaticity() {
+        assert!(CHRM::new((-0.1, 0.3), (0.64, 0.33), (0.30, 0.60), (0.15, 0.06)).is_err());
+        assert!(CHRM::new((0.3, 0.3), (0.64, f64::NAN), (0.30, 0.60), (0.15, 0.06)).is_err());
+
+        let chunk = PNGChunk::new("cHRM", &[0; 28]).unwrap();
+        assert!(CHRM::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+    }
+
+    #[test]
+    fn validate_chromaticity() {
+        let chunk = CHRM::new((0.3127, 0.3290), (0.64, 0.33), (0.30, 0.60), (0.15, 0.06)).unwrap();
+        assert!(CHRM::from_chunk_refs(&chunk.as_chunk_refs()).unwrap().validate().is_ok());
+
+        let chunk = CHRM::new((1.0, 1.0), (0.0, 0.0), (0.30, 0.60), (0.15, 0.06)).unwrap();
+        assert!(CHRM::from_chunk_refs(&chunk.as_chunk_refs()).unwrap().validate().is_ok());
+
+        let chunk = CHRM::new((0.3127, 0.3290), (1.5, 0.33), (0.30, 0.60), (0.15, 0.06)).unwrap();
+        assert!(CHRM::from_chunk_refs(&chunk.as_chunk_refs()).unwrap().validate().is_err());
+    }
+}
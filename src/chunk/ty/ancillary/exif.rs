@@ -0,0 +1,71 @@
+use crate::chunk::refs::ChunkRefs;
+use crate::error::PngError;
+use alloc::string::ToString;
+
+/// The eXIf chunk holds an Exif profile, which is a TIFF formatted block of camera and image metadata,
+/// starting with the "MM" or "II" byte order mark. The data is exposed as-is, so that it can be handed
+/// to an Exif parser.
+#[allow(clippy::upper_case_acronyms)]
+pub struct EXIF<'a> {
+    /// The Exif profile, without the "Exif\0\0" header that JPEG files use.
+    data: &'a [u8],
+}
+
+impl<'a> EXIF<'a> {
+    /// Validates that the Exif data starts with a TIFF header, which is either "MM\0*" for big endian
+    /// data or "II*\0" for little endian data.
+    pub fn validate(&self) -> Result<(), PngError> {
+        if !self.data.starts_with(b"MM\0*") && !self.data.starts_with(b"II*\0") {
+            return Err(PngError::InvalidData(
+                "eXIf data does not start with a TIFF byte order mark.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the raw Exif data.
+    #[inline(always)]
+    pub fn get_exif_data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+// Associated functions
+impl<'a> EXIF<'a> {
+    /// Provides a view of the Exif data of an eXIf chunk. Returns `None` if the chunk type is not
+    /// "eXIf". The Exif data is not parsed.
+    pub fn from_chunk_refs(chunk_refs: &ChunkRefs<'a>) -> Option<EXIF<'a>> {
+        if chunk_refs.get_chunk_type() != "eXIf" {
+            return None;
+        }
+
+        Some(EXIF {
+            data: chunk_refs.get_chunk_data(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::chunk::ty::ancillary::exif::EXIF;
+    use crate::PNGReader;
+
+    #[test]
+    fn exif_data() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let chunk = png.get_chunk_of_type("eXIf").unwrap();
+        let exif = EXIF::from_chunk_refs(&chunk).unwrap();
+        assert_eq!(exif.get_exif_data(), chunk.get_chunk_data());
+        assert!(exif.get_exif_data().starts_with(b"MM\0*"));
+        assert!(exif.validate().is_ok());
+        assert_eq!(png.exif(), Some(chunk.get_chunk_data()));
+
+        let srgb = png.get_chunk_of_type("sRGB").unwrap();
+        assert!(EXIF::from_chunk_refs(&srgb).is_none());
+
+        let stripped = png.strip_ancillary().unwrap();
+        assert_eq!(PNGReader::new(&stripped[..]).unwrap().exif(), None);
+    }
+}
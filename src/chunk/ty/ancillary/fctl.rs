@@ -0,0 +1,220 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use alloc::format;
+use alloc::vec::Vec;
+use bytemuck::AnyBitPattern;
+
+/// The fcTL chunk is part of the APNG extension. It describes the region, delay, disposal and blending
+/// of the frame whose data follows it, either in IDAT chunks for the first frame, or in fdAT chunks for
+/// every frame after that.
+#[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct FCTL {
+    /// Sequence number of the animation chunk, starting from 0. Shared with fdAT chunks.
+    sequence_number: [u8; 4],
+    /// Width of the following frame. 4-byte unsigned integer.
+    width: [u8; 4],
+    /// Height of the following frame. 4-byte unsigned integer.
+    height: [u8; 4],
+    /// X position at which to render the following frame. 4-byte unsigned integer.
+    x_offset: [u8; 4],
+    /// Y position at which to render the following frame. 4-byte unsigned integer.
+    y_offset: [u8; 4],
+    /// Frame delay fraction numerator. 2-byte unsigned integer.
+    delay_num: [u8; 2],
+    /// Frame delay fraction denominator. 2-byte unsigned integer. 0 is treated as 100.
+    delay_den: [u8; 2],
+    /// Type of frame area disposal to be done after rendering this frame. 0 (none), 1 (background) or
+    /// 2 (previous).
+    dispose_op: u8,
+    /// Type of frame area rendering for this frame. 0 (source) or 1 (over).
+    blend_op: u8,
+}
+
+const FCTL_SIZE: usize = 26;
+const _: () = assert!(core::mem::size_of::<FCTL>() == FCTL_SIZE);
+/// The delay denominator used when the stored denominator is 0.
+const DEFAULT_DELAY_DEN: u16 = 100;
+/// The highest valid dispose op (previous).
+pub const MAX_DISPOSE_OP: u8 = 2;
+/// The highest valid blend op (over).
+pub const MAX_BLEND_OP: u8 = 1;
+
+impl FCTL {
+    /// Checks that the dispose op is 0-2 and the blend op is 0-1.
+    pub fn validate(&self) -> Result<(), PngError> {
+        if self.dispose_op > MAX_DISPOSE_OP {
+            return Err(PngError::InvalidData(
+                format!("Invalid dispose op. Must be 0-{MAX_DISPOSE_OP}. dispose op: {}",
+                        self.dispose_op,
+                ),
+            ));
+        }
+
+        if self.blend_op > MAX_BLEND_OP {
+            return Err(PngError::InvalidData(
+                format!("Invalid blend op. Must be 0-{MAX_BLEND_OP}. blend op: {}",
+                        self.blend_op,
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the sequence number field value
+    #[inline(always)]
+    pub fn get_sequence_number(&self) -> u32 {
+        u32::from_be_bytes(self.sequence_number)
+    }
+    /// Gets the width field value
+    #[inline(always)]
+    pub fn get_width(&self) -> u32 {
+        u32::from_be_bytes(self.width)
+    }
+    /// Gets the height field value
+    #[inline(always)]
+    pub fn get_height(&self) -> u32 {
+        u32::from_be_bytes(self.height)
+    }
+    /// Gets the x offset field value
+    #[inline(always)]
+    pub fn get_x_offset(&self) -> u32 {
+        u32::from_be_bytes(self.x_offset)
+    }
+    /// Gets the y offset field value
+    #[inline(always)]
+    pub fn get_y_offset(&self) -> u32 {
+        u32::from_be_bytes(self.y_offset)
+    }
+    /// Gets the delay numerator field value
+    #[inline(always)]
+    pub fn get_delay_num(&self) -> u16 {
+        u16::from_be_bytes(self.delay_num)
+    }
+    /// Gets the delay denominator field value
+    #[inline(always)]
+    pub fn get_delay_den(&self) -> u16 {
+        u16::from_be_bytes(self.delay_den)
+    }
+    /// Gets the frame delay in seconds. A denominator of 0 is treated as 100, so the numerator is in
+    /// hundredths of a second.
+    pub fn get_delay_seconds(&self) -> f64 {
+        let delay_den = match self.get_delay_den() {
+            0 => DEFAULT_DELAY_DEN,
+            delay_den => delay_den,
+        };
+
+        self.get_delay_num() as f64 / delay_den as f64
+    }
+    /// Gets the dispose op field value
+    #[inline(always)]
+    pub fn get_dispose_op(&self) -> u8 {
+        self.dispose_op
+    }
+    /// Gets the blend op field value
+    #[inline(always)]
+    pub fn get_blend_op(&self) -> u8 {
+        self.blend_op
+    }
+}
+
+// Associated functions
+impl FCTL {
+    /// Creates a new fcTL `PNGChunk` with the provided values. Fails if the dispose op or blend op is
+    /// invalid.
+    #[allow(clippy::new_ret_no_self)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sequence_number: u32,
+        width: u32,
+        height: u32,
+        x_offset: u32,
+        y_offset: u32,
+        delay_num: u16,
+        delay_den: u16,
+        dispose_op: u8,
+        blend_op: u8,
+    ) -> Result<PNGChunk, PngError> {
+        let frame_control = FCTL {
+            sequence_number: sequence_number.to_be_bytes(),
+            width: width.to_be_bytes(),
+            height: height.to_be_bytes(),
+            x_offset: x_offset.to_be_bytes(),
+            y_offset: y_offset.to_be_bytes(),
+            delay_num: delay_num.to_be_bytes(),
+            delay_den: delay_den.to_be_bytes(),
+            dispose_op,
+            blend_op,
+        };
+        frame_control.validate()?;
+
+        let mut data = Vec::with_capacity(FCTL_SIZE);
+        data.extend(frame_control.sequence_number);
+        data.extend(frame_control.width);
+        data.extend(frame_control.height);
+        data.extend(frame_control.x_offset);
+        data.extend(frame_control.y_offset);
+        data.extend(frame_control.delay_num);
+        data.extend(frame_control.delay_den);
+        data.push(frame_control.dispose_op);
+        data.push(frame_control.blend_op);
+
+        PNGChunk::new("fcTL", &data)
+    }
+    /// Provides a reference to a FCTL provided the chunk_type matches "fcTL" and the size of the data
+    /// matches the size of FCTL.
+    pub fn from_chunk_refs<'a>(chunk_refs: &'a ChunkRefs<'a>) -> Option<&'a FCTL> {
+        if chunk_refs.get_chunk_type() != "fcTL" {
+            return None;
+        }
+
+        // Checks the size and alignment, instead of casting the pointer.
+        bytemuck::try_from_bytes(chunk_refs.get_chunk_data()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::fctl::FCTL;
+    use crate::chunk::PNGChunk;
+
+    #[test]
+    fn frame_control_round_trip() {
+        let chunk = FCTL::new(4, 10, 20, 1, 2, 1, 30, 1, 1).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let frame_control = FCTL::from_chunk_refs(&refs).unwrap();
+        frame_control.validate().unwrap();
+
+        assert_eq!(frame_control.get_sequence_number(), 4);
+        assert_eq!(frame_control.get_width(), 10);
+        assert_eq!(frame_control.get_height(), 20);
+        assert_eq!(frame_control.get_x_offset(), 1);
+        assert_eq!(frame_control.get_y_offset(), 2);
+        assert_eq!(frame_control.get_delay_num(), 1);
+        assert_eq!(frame_control.get_delay_den(), 30);
+        assert_eq!(frame_control.get_dispose_op(), 1);
+        assert_eq!(frame_control.get_blend_op(), 1);
+        assert_eq!(frame_control.get_delay_seconds(), 1.0 / 30.0);
+    }
+
+    #[test]
+    fn default_delay_denominator() {
+        let chunk = FCTL::new(0, 1, 1, 0, 0, 25, 0, 0, 0).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let frame_control = FCTL::from_chunk_refs(&refs).unwrap();
+
+        assert_eq!(frame_control.get_delay_den(), 0);
+        assert_eq!(frame_control.get_delay_seconds(), 0.25);
+    }
+
+    #[test]
+    fn invalid_frame_control() {
+        assert!(FCTL::new(0, 1, 1, 0, 0, 0, 0, 3, 0).is_err());
+        assert!(FCTL::new(0, 1, 1, 0, 0, 0, 0, 0, 2).is_err());
+
+        let chunk = PNGChunk::new("fcTL", &[0; 25]).unwrap();
+        assert!(FCTL::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+    }
+}
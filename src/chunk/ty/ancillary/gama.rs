@@ -0,0 +1,105 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use alloc::format;
+use alloc::string::ToString;
+use bytemuck::AnyBitPattern;
+
+/// The gAMA chunk specifies the relationship between the image samples and the desired display output
+/// intensity. The value is stored as a 4-byte unsigned integer, representing gamma times 100000. For
+/// example, a gamma of 1/2.2 would be stored as 45455.
+#[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct GAMA {
+    /// Image gamma times 100000.
+    gamma: [u8; 4],
+}
+
+const GAMA_SIZE: usize = 4;
+const _: () = assert!(core::mem::size_of::<GAMA>() == GAMA_SIZE);
+/// The gamma is stored as an integer, scaled by this value.
+const GAMMA_SCALE: f64 = 100000.0;
+
+impl GAMA {
+    /// Checks that the gamma is not 0, which is meaningless.
+    pub fn validate(&self) -> Result<(), PngError> {
+        if self.get_gamma_raw() == 0 {
+            return Err(PngError::InvalidData(
+                "Invalid gamma. Gamma cannot be 0.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the gamma field value, which is the image gamma times 100000.
+    #[inline(always)]
+    pub fn get_gamma_raw(&self) -> u32 {
+        u32::from_be_bytes(self.gamma)
+    }
+    /// Gets the image gamma.
+    #[inline(always)]
+    pub fn get_gamma(&self) -> f64 {
+        self.get_gamma_raw() as f64 / GAMMA_SCALE
+    }
+}
+
+// Associated functions
+impl GAMA {
+    /// Creates a new gAMA `PNGChunk` for the provided gamma. The gamma is multiplied by 100000 and rounded.
+    /// Fails if the result does not fit in a u32.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(gamma: f64) -> Result<PNGChunk, PngError> {
+        let scaled = gamma * GAMMA_SCALE;
+        if !(0.0..=u32::MAX as f64).contains(&scaled) {
+            return Err(PngError::InvalidData(
+                format!("Gamma is out of range. Gamma times 100000 must fit in a u32. gamma: {gamma}"),
+            ));
+        }
+
+        // `f64::round` is not in core. The value is not negative, so adding 0.5 and truncating rounds the
+        // same way.
+        PNGChunk::new("gAMA", &((scaled + 0.5) as u32).to_be_bytes())
+    }
+    /// Provides a reference to a GAMA provided the chunk_type matches "gAMA" and the size of the data
+    /// matches the size of GAMA.
+    pub fn from_chunk_refs<'a>(chunk_refs: &'a ChunkRefs<'a>) -> Option<&'a GAMA> {
+        if chunk_refs.get_chunk_type() != "gAMA" {
+            return None;
+        }
+
+        // Checks the size and alignment, instead of casting the pointer.
+        bytemuck::try_from_bytes(chunk_refs.get_chunk_data()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::gama::GAMA;
+    use crate::chunk::PNGChunk;
+
+    #[test]
+    fn gamma_round_trip() {
+        let chunk = GAMA::new(1.0 / 2.2).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let gamma = GAMA::from_chunk_refs(&refs).unwrap();
+        gamma.validate().unwrap();
+
+        assert_eq!(gamma.get_gamma_raw(), 45455);
+        assert!((gamma.get_gamma() - 0.45455).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn invalid_gamma() {
+        assert!(GAMA::new(-1.0).is_err());
+        assert!(GAMA::new(f64::NAN).is_err());
+        assert!(GAMA::new(50000.0).is_err());
+
+        let chunk = PNGChunk::new("gAMA", &[0, 0, 177]).unwrap();
+        assert!(GAMA::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+
+        let chunk = GAMA::new(0.0).unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(GAMA::from_chunk_refs(&refs).unwrap().validate().is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use alloc::borrow::Cow;
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::ty::ancillary::text::{decode_latin1, TEXt};
+use crate::chunk::ty::ancillary::ztxt::COMPRESSION_METHOD_DEFLATE;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// The iTXt chunk contains international textual data. Like tEXt and zTXt, it has a Latin-1 keyword,
+/// but the text is UTF-8, and can optionally be compressed. It can also carry a language tag and a
+/// translation of the keyword into that language.
+pub struct ITXt<'a> {
+    /// Keyword. 1-79 bytes, printable Latin-1 characters and spaces.
+    keyword: &'a [u8],
+    /// Compression flag. 0 for uncompressed text, 1 for compressed text.
+    compression_flag: u8,
+    /// Compression method. 0 (zlib datastream with deflate compression) is the only method defined.
+    compression_method: u8,
+    /// Language tag. An RFC 3066 language tag, like "en" or "x-klingon". Can be empty.
+    language_tag: &'a [u8],
+    /// Translated keyword. The keyword translated into the language of the language tag, in UTF-8. Can
+    /// be empty.
+    translated_keyword: &'a [u8],
+    /// Text. UTF-8 text, which is a zlib datastream when the compression flag is 1. There is no null
+    /// terminator.
+    text: &'a [u8],
+}
+
+const MAX_KEYWORD_LENGTH: usize = 79;
+
+impl<'a> ITXt<'a> {
+    /// Checks that the compression flag is 0 or 1, and that the compression method is 0 when the text is
+    /// compressed.
+    pub fn validate(&self) -> Result<(), PngError> {
+        if self.compression_flag > 1 {
+            return Err(PngError::InvalidData(
+                format!("Invalid compression flag. Must be 0 or 1. compression flag: {}",
+                        self.compression_flag,
+                ),
+            ));
+        }
+        if self.is_compressed() && self.compression_method != COMPRESSION_METHOD_DEFLATE {
+            return Err(PngError::InvalidData(
+                format!("Invalid compression method. Must be 0. compression method: {}",
+                        self.compression_method,
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the keyword, transcoded from Latin-1. Fails if the keyword does not follow the restrictions
+    /// in `TEXt::validate_keyword`.
+    pub fn get_keyword(&self) -> Result<Cow<'a, str>, PngError> {
+        let keyword = decode_latin1(self.keyword);
+        TEXt::validate_keyword(&keyword)?;
+
+        Ok(keyword)
+    }
+    /// Gets the compression flag field value
+    #[inline(always)]
+    pub fn get_compression_flag(&self) -> u8 {
+        self.compression_flag
+    }
+    /// Gets the compression method field value
+    #[inline(always)]
+    pub fn get_compression_method(&self) -> u8 {
+        self.compression_method
+    }
+    /// Returns true if the compression flag is set.
+    #[inline(always)]
+    pub fn is_compressed(&self) -> bool {
+        self.compression_flag == 1
+    }
+    /// Gets the language tag. Fails if the language tag is not ASCII.
+    pub fn get_language_tag(&self) -> Result<&'a str, PngError> {
+        if !self.language_tag.is_ascii() {
+            return Err(PngError::InvalidData(
+                "Invalid language tag. Language tags must be ASCII.".to_string(),
+            ));
+        }
+
+        Ok(unsafe { core::str::from_utf8_unchecked(self.language_tag) })
+    }
+    /// Gets the translated keyword. Fails if the translated keyword is not valid UTF-8.
+    pub fn get_translated_keyword(&self) -> Result<&'a str, PngError> {
+        core::str::from_utf8(self.translated_keyword).map_err(|e| PngError::InvalidData(
+            format!("Translated keyword is not valid UTF-8. {e}"),
+        ))
+    }
+    /// Gets the raw text bytes. This is a zlib datastream if the compression flag is set.
+    #[inline(always)]
+    pub fn get_text_bytes(&self) -> &'a [u8] {
+        self.text
+    }
+    /// Gets the text, decompressing it if the compression flag is set. Fails if the chunk does not pass
+    /// `validate`, if the text is not valid UTF-8, or if the text is compressed and the compressed data is
+    /// not a valid zlib datastream or decompresses to more than `MAX_DECOMPRESSED_TEXT_LENGTH` bytes.
+    /// Compressed text can only be read with the `text-decode` feature.
+    pub fn get_text(&self) -> Result<String, PngError> {
+        self.validate()?;
+
+        let text = if self.is_compressed() {
+            Self::inflate(self.text)?
+        } else {
+            self.text.to_vec()
+        };
+
+        String::from_utf8(text).map_err(|e| PngError::InvalidData(
+            format!("iTXt text is not valid UTF-8. {e}"),
+        ))
+    }
+}
+
+// Associated functions
+impl<'a> ITXt<'a> {
+    /// Parses the data of an iTXt chunk. Returns `None` if the chunk type is not "iTXt", if the keyword is
+    /// empty or longer than 79 bytes, or if the compression bytes or any of the null separators are
+    /// missing.
+    pub fn from_chunk_refs(chunk_refs: &ChunkRefs<'a>) -> Option<ITXt<'a>> {
+        if chunk_refs.get_chunk_type() != "iTXt" {
+            return None;
+        }
+
+        let data = chunk_refs.get_chunk_data();
+        let (keyword, rest) = split_null(data)?;
+        if keyword.is_empty() || keyword.len() > MAX_KEYWORD_LENGTH {
+            return None;
+        }
+
+        let (&compression_flag, rest) = rest.split_first()?;
+        let (&compression_method, rest) = rest.split_first()?;
+        let (language_tag, rest) = split_null(rest)?;
+        let (translated_keyword, text) = split_null(rest)?;
+
+        Some(ITXt {
+            keyword,
+            compression_flag,
+            compression_method,
+            language_tag,
+            translated_keyword,
+            text,
+        })
+    }
+    #[cfg(feature = "text-decode")]
+    fn inflate(compressed: &[u8]) -> Result<Vec<u8>, PngError> {
+        crate::chunk::ty::ancillary::ztxt::inflate(compressed)
+    }
+    #[cfg(not(feature = "text-decode"))]
+    fn inflate(_compressed: &[u8]) -> Result<Vec<u8>, PngError> {
+        Err(PngError::Unsupported(
+            "Compressed iTXt text requires the text-decode feature".to_string(),
+        ))
+    }
+}
+
+/// Splits the buffer on the first null byte, dropping the null byte.
+fn split_null(buffer: &[u8]) -> Option<(&[u8], &[u8])> {
+    let null = buffer.iter().position(|&b| b == 0)?;
+    Some((&buffer[..null], &buffer[null + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::itxt::ITXt;
+    use crate::chunk::PNGChunk;
+    #[cfg(feature = "std")]
+    use crate::PNGReader;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_international_text() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let itxt_chunk = png.get_chunk_of_type("iTXt").unwrap();
+        let itxt = ITXt::from_chunk_refs(&itxt_chunk).unwrap();
+        itxt.validate().unwrap();
+
+        assert_eq!(itxt.get_keyword().unwrap(), "XML:com.adobe.xmp");
+        assert!(!itxt.is_compressed());
+        assert_eq!(itxt.get_language_tag().unwrap(), "");
+        assert_eq!(itxt.get_translated_keyword().unwrap(), "");
+        assert!(itxt.get_text().unwrap().starts_with("<x:xmpmeta"));
+    }
+
+    #[test]
+    fn read_translated_text() {
+        let chunk = PNGChunk::new("iTXt", "Title\0\0\0fr\0Titre\0Caf\u{E9} \u{2615}".as_bytes()).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let itxt = ITXt::from_chunk_refs(&refs).unwrap();
+
+        assert_eq!(itxt.get_keyword().unwrap(), "Title");
+        assert_eq!(itxt.get_language_tag().unwrap(), "fr");
+        assert_eq!(itxt.get_translated_keyword().unwrap(), "Titre");
+        assert_eq!(itxt.get_text().unwrap(), "Caf\u{E9} \u{2615}");
+    }
+
+    #[test]
+    #[cfg(feature = "text-decode")]
+    fn read_compressed_international_text() {
+        // "Made with love for the crab", compressed with zlib at level 9.
+        let mut data = b"Comment\0\x01\x00en\0\0".to_vec();
+        data.extend([
+            120, 218, 243, 77, 76, 73, 85, 40, 207, 44, 201, 80, 200, 201, 47, 75, 85, 72, 203, 47, 82, 40,
+            201, 72, 85, 72, 46, 74, 76, 2, 0, 134, 216, 9, 170,
+        ]);
+        let chunk = PNGChunk::new("iTXt", &data).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let itxt = ITXt::from_chunk_refs(&refs).unwrap();
+
+        assert!(itxt.is_compressed());
+        assert_eq!(itxt.get_text().unwrap(), "Made with love for the crab");
+    }
+
+    #[test]
+    fn invalid_international_text() {
+        let missing_separators = [
+            &b"Title"[..],
+            b"Title\0",
+            b"Title\0\0",
+            b"Title\0\0\0en",
+            b"Title\0\0\0en\0Titre",
+        ];
+        for data in missing_separators {
+            let chunk = PNGChunk::new("iTXt", data).unwrap();
+            assert!(ITXt::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+        }
+
+        let chunk = PNGChunk::new("iTXt", b"Title\0\x02\0\0\0text").unwrap();
+        let refs = chunk.as_chunk_refs();
+        let itxt = ITXt::from_chunk_refs(&refs).unwrap();
+        assert!(itxt.validate().is_err());
+        assert!(itxt.get_text().is_err());
+
+        let chunk = PNGChunk::new("iTXt", b"Title\0\x01\x01\0\0text").unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(ITXt::from_chunk_refs(&refs).unwrap().validate().is_err());
+
+        let chunk = PNGChunk::new("iTXt", b"Title\0\0\0\0\0\xFF").unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(ITXt::from_chunk_refs(&refs).unwrap().get_text().is_err());
+    }
+}
@@ -0,0 +1,15 @@
+pub mod actl;
+pub mod bkgd;
+pub mod chrm;
+pub mod exif;
+pub mod fctl;
+pub mod gama;
+pub mod itxt;
+pub mod offs;
+pub mod phys;
+pub mod sbit;
+pub mod splt;
+pub mod srgb;
+pub mod text;
+pub mod time;
+pub mod ztxt;
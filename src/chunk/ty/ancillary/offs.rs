@@ -0,0 +1,109 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use alloc::format;
+use alloc::vec::Vec;
+use bytemuck::AnyBitPattern;
+
+/// The oFFs chunk gives the position on a printed page at which the image should be output when printed
+/// alone. It can also be used to define the image's location with respect to a larger screen or other
+/// application-specific coordinate system.
+#[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct OFFS {
+    /// Position on the X axis. 4-byte signed integer.
+    x_position: [u8; 4],
+    /// Position on the Y axis. 4-byte signed integer.
+    y_position: [u8; 4],
+    /// Unit specifier. 0 (unit is the pixel) or 1 (unit is the micrometer).
+    unit: u8,
+}
+
+const OFFS_SIZE: usize = 9;
+const _: () = assert!(core::mem::size_of::<OFFS>() == OFFS_SIZE);
+/// The unit specifier value for pixels.
+pub const UNIT_PIXEL: u8 = 0;
+/// The unit specifier value for micrometers.
+pub const UNIT_MICROMETER: u8 = 1;
+
+impl OFFS {
+    /// Checks that the unit specifier is 0 (pixel) or 1 (micrometer).
+    pub fn validate(&self) -> Result<(), PngError> {
+        if self.unit > UNIT_MICROMETER {
+            return Err(PngError::InvalidData(
+                format!("Invalid unit specifier. Must be 0 (pixel) or 1 (micrometer). unit: {}",
+                        self.unit,
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the position on the X axis.
+    #[inline(always)]
+    pub fn get_x_position(&self) -> i32 {
+        i32::from_be_bytes(self.x_position)
+    }
+    /// Gets the position on the Y axis.
+    #[inline(always)]
+    pub fn get_y_position(&self) -> i32 {
+        i32::from_be_bytes(self.y_position)
+    }
+    /// Gets the unit specifier. 0 means the unit is the pixel, 1 means the unit is the micrometer.
+    #[inline(always)]
+    pub fn get_unit(&self) -> u8 {
+        self.unit
+    }
+}
+
+// Associated functions
+impl OFFS {
+    /// Creates a new oFFs `PNGChunk` with the provided position and unit specifier. Fails if the unit
+    /// specifier is not 0 (pixel) or 1 (micrometer).
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(x_position: i32, y_position: i32, unit: u8) -> Result<PNGChunk, PngError> {
+        let offset = OFFS {
+            x_position: x_position.to_be_bytes(),
+            y_position: y_position.to_be_bytes(),
+            unit,
+        };
+        offset.validate()?;
+
+        let mut data = Vec::with_capacity(OFFS_SIZE);
+        data.extend(offset.x_position);
+        data.extend(offset.y_position);
+        data.push(offset.unit);
+
+        PNGChunk::new("oFFs", &data)
+    }
+    /// Provides a reference to a OFFS provided the chunk_type matches "oFFs" and the size of the data
+    /// matches the size of OFFS.
+    pub fn from_chunk_refs<'a>(chunk_refs: &'a ChunkRefs<'a>) -> Option<&'a OFFS> {
+        if chunk_refs.get_chunk_type() != "oFFs" {
+            return None;
+        }
+
+        // Checks the size and alignment, instead of casting the pointer.
+        bytemuck::try_from_bytes(chunk_refs.get_chunk_data()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::offs::{OFFS, UNIT_MICROMETER};
+
+    #[test]
+    fn offset_round_trip() {
+        let chunk = OFFS::new(-25400, 50800, UNIT_MICROMETER).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let offset = OFFS::from_chunk_refs(&refs).unwrap();
+        offset.validate().unwrap();
+
+        assert_eq!(offset.get_x_position(), -25400);
+        assert_eq!(offset.get_y_position(), 50800);
+        assert_eq!(offset.get_unit(), UNIT_MICROMETER);
+
+        assert!(OFFS::new(0, 0, 2).is_err());
+    }
+}
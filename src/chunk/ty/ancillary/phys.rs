@@ -0,0 +1,144 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use alloc::format;
+use alloc::vec::Vec;
+use bytemuck::AnyBitPattern;
+
+/// The pHYs chunk specifies the intended pixel size or aspect ratio for display of the image.
+///
+/// When the unit specifier is 0, the pHYs chunk defines pixel aspect ratio only; the actual size of the
+/// pixels remains unspecified.
+#[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct PHYS {
+    /// Pixels per unit, X axis. 4-byte unsigned integer.
+    x_ppu: [u8; 4],
+    /// Pixels per unit, Y axis. 4-byte unsigned integer.
+    y_ppu: [u8; 4],
+    /// Unit specifier. 0 (unit is unknown) or 1 (unit is the meter).
+    unit: u8,
+}
+
+const PHYS_SIZE: usize = 9;
+const _: () = assert!(core::mem::size_of::<PHYS>() == PHYS_SIZE);
+/// The unit specifier value for meters.
+pub const UNIT_METER: u8 = 1;
+/// Meters per inch, used to convert pixels per meter to dots per inch.
+const METERS_PER_INCH: f64 = 0.0254;
+
+impl PHYS {
+    /// Checks that the unit specifier is 0 (unknown) or 1 (meter).
+    pub fn validate(&self) -> Result<(), PngError> {
+        if self.unit > UNIT_METER {
+            return Err(PngError::InvalidData(
+                format!("Invalid unit specifier. Must be 0 (unknown) or 1 (meter). unit: {}",
+                        self.unit,
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the pixels per unit on the X axis.
+    #[inline(always)]
+    pub fn get_x_ppu(&self) -> u32 {
+        u32::from_be_bytes(self.x_ppu)
+    }
+    /// Gets the pixels per unit on the Y axis.
+    #[inline(always)]
+    pub fn get_y_ppu(&self) -> u32 {
+        u32::from_be_bytes(self.y_ppu)
+    }
+    /// Gets the unit specifier. 0 means the unit is unknown, 1 means the unit is the meter.
+    #[inline(always)]
+    pub fn get_unit(&self) -> u8 {
+        self.unit
+    }
+    /// Gets the dots per inch on the X and Y axis. Only returns a value when the unit is the meter, since
+    /// an unknown unit only defines the pixel aspect ratio.
+    pub fn get_dpi(&self) -> Option<(f64, f64)> {
+        if self.unit != UNIT_METER {
+            return None;
+        }
+
+        Some((
+            self.get_x_ppu() as f64 * METERS_PER_INCH,
+            self.get_y_ppu() as f64 * METERS_PER_INCH,
+        ))
+    }
+}
+
+// Associated functions
+impl PHYS {
+    /// Creates a new pHYs `PNGChunk` with the provided pixels per unit and unit specifier. Fails if the
+    /// unit specifier is not 0 (unknown) or 1 (meter).
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(x_ppu: u32, y_ppu: u32, unit: u8) -> Result<PNGChunk, PngError> {
+        let physical = PHYS {
+            x_ppu: x_ppu.to_be_bytes(),
+            y_ppu: y_ppu.to_be_bytes(),
+            unit,
+        };
+        physical.validate()?;
+
+        let mut data = Vec::with_capacity(PHYS_SIZE);
+        data.extend(physical.x_ppu);
+        data.extend(physical.y_ppu);
+        data.push(physical.unit);
+
+        PNGChunk::new("pHYs", &data)
+    }
+    /// Provides a reference to a PHYS provided the chunk_type matches "pHYs" and the size of the data
+    /// matches the size of PHYS.
+    pub fn from_chunk_refs<'a>(chunk_refs: &'a ChunkRefs<'a>) -> Option<&'a PHYS> {
+        if chunk_refs.get_chunk_type() != "pHYs" {
+            return None;
+        }
+
+        // Checks the size and alignment, instead of casting the pointer.
+        bytemuck::try_from_bytes(chunk_refs.get_chunk_data()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::phys::{PHYS, UNIT_METER};
+    #[cfg(feature = "std")]
+    use crate::PNGReader;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_physical_dimensions() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let phys_chunk = png.get_chunk_of_type("pHYs").unwrap();
+        let physical = PHYS::from_chunk_refs(&phys_chunk).unwrap();
+        physical.validate().unwrap();
+
+        assert_eq!(physical.get_x_ppu(), 2835);
+        assert_eq!(physical.get_y_ppu(), 2835);
+        assert_eq!(physical.get_unit(), UNIT_METER);
+    }
+
+    #[test]
+    fn dpi_round_trip() {
+        // 72 DPI is 2834.6 pixels per meter
+        let ppm = (72.0 / 0.0254f64).round() as u32;
+        let chunk = PHYS::new(ppm, ppm, UNIT_METER).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let physical = PHYS::from_chunk_refs(&refs).unwrap();
+
+        let (x_dpi, y_dpi) = physical.get_dpi().unwrap();
+        assert!((x_dpi - 72.0).abs() < 0.01);
+        assert!((y_dpi - 72.0).abs() < 0.01);
+
+        let chunk = PHYS::new(1, 2, 0).unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(PHYS::from_chunk_refs(&refs).unwrap().get_dpi().is_none());
+
+        assert!(PHYS::new(ppm, ppm, 2).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use alloc::format;
+
+/// The sBIT chunk stores the number of significant bits in the original image data for each channel,
+/// which lets a decoder recover the original data losslessly when the samples were scaled up to a
+/// supported bit depth. The layout of the chunk depends on the color type in the IHDR chunk, so a view
+/// has to be created with the color type.
+///
+/// | Color type | Contents                  |
+/// |------------|---------------------------|
+/// | 0          | gray                      |
+/// | 2, 3       | red, green, blue          |
+/// | 4          | gray, alpha               |
+/// | 6          | red, green, blue, alpha   |
+#[derive(Debug, Copy, Clone)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct SBIT<'a> {
+    /// The color type from the IHDR chunk.
+    color_type: u8,
+    /// One significant bit count per channel.
+    data: &'a [u8],
+}
+
+impl SBIT<'_> {
+    /// Checks that the data length matches the color type, and that each significant bit count is between
+    /// 1 and the provided sample depth. The sample depth is the IHDR bit depth, except for color type 3,
+    /// where it is always 8. See `IHDRDetails::get_sample_depth`.
+    pub fn validate(&self, sample_depth: u8) -> Result<(), PngError> {
+        let expected_len = Self::get_data_size(self.color_type).ok_or_else(|| PngError::InvalidData(
+            format!("Invalid color type. color type: {}", self.color_type),
+        ))?;
+        if self.data.len() != expected_len {
+            return Err(PngError::InvalidData(
+                format!("Invalid sBIT length for color type. Expected: {expected_len} color type: {} length: {}",
+                        self.color_type,
+                        self.data.len(),
+                ),
+            ));
+        }
+
+        for &significant_bits in self.data {
+            if !(1..=sample_depth).contains(&significant_bits) {
+                return Err(PngError::InvalidData(
+                    format!("Invalid significant bits. Must be 1-{sample_depth}. significant bits: {significant_bits}"),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+    /// Gets the color type this view was read with.
+    #[inline(always)]
+    pub fn get_color_type(&self) -> u8 {
+        self.color_type
+    }
+    /// Gets the significant bits of the gray channel. Returns `None` if the color type is not 0 or 4.
+    pub fn get_gray(&self) -> Option<u8> {
+        match self.color_type {
+            0 | 4 => self.data.first().copied(),
+            _ => None,
+        }
+    }
+    /// Gets the significant bits of the red channel. Returns `None` if the color type is not 2, 3 or 6.
+    pub fn get_red(&self) -> Option<u8> {
+        self.get_color_channel(0)
+    }
+    /// Gets the significant bits of the green channel. Returns `None` if the color type is not 2, 3 or 6.
+    pub fn get_green(&self) -> Option<u8> {
+        self.get_color_channel(1)
+    }
+    /// Gets the significant bits of the blue channel. Returns `None` if the color type is not 2, 3 or 6.
+    pub fn get_blue(&self) -> Option<u8> {
+        self.get_color_channel(2)
+    }
+    /// Gets the significant bits of the alpha channel. Returns `None` if the color type is not 4 or 6.
+    pub fn get_alpha(&self) -> Option<u8> {
+        match self.color_type {
+            4 => self.data.get(1).copied(),
+            6 => self.data.get(3).copied(),
+            _ => None,
+        }
+    }
+    /// Gets the significant bits of the red, green or blue channel, for color types that have them.
+    fn get_color_channel(&self, index: usize) -> Option<u8> {
+        match self.color_type {
+            2 | 3 | 6 => self.data.get(index).copied(),
+            _ => None,
+        }
+    }
+}
+
+// Associated functions
+impl<'a> SBIT<'a> {
+    /// Provides a view over an sBIT chunk, provided the chunk_type matches "sBIT". The data length is not
+    /// checked against the color type until `validate` is called. Accessors return `None` for channels
+    /// that are missing from the data.
+    pub fn from_chunk_refs(chunk_refs: &ChunkRefs<'a>, color_type: u8) -> Option<SBIT<'a>> {
+        if chunk_refs.get_chunk_type() != "sBIT" {
+            return None;
+        }
+
+        Some(SBIT {
+            color_type,
+            data: chunk_refs.get_chunk_data(),
+        })
+    }
+    /// Gets the size of the sBIT data for the provided color type. Returns `None` for an invalid color
+    /// type.
+    pub fn get_data_size(color_type: u8) -> Option<usize> {
+        match color_type {
+            0 => Some(1),
+            4 => Some(2),
+            2 | 3 => Some(3),
+            6 => Some(4),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::sbit::SBIT;
+    use crate::chunk::PNGChunk;
+
+    #[test]
+    fn significant_bits() {
+        let chunk = PNGChunk::new("sBIT", &[5, 6, 5, 8]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let significant_bits = SBIT::from_chunk_refs(&refs, 6).unwrap();
+        significant_bits.validate(8).unwrap();
+
+        assert_eq!(significant_bits.get_red(), Some(5));
+        assert_eq!(significant_bits.get_green(), Some(6));
+        assert_eq!(significant_bits.get_blue(), Some(5));
+        assert_eq!(significant_bits.get_alpha(), Some(8));
+        assert_eq!(significant_bits.get_gray(), None);
+
+        let chunk = PNGChunk::new("sBIT", &[12, 1]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let significant_bits = SBIT::from_chunk_refs(&refs, 4).unwrap();
+        significant_bits.validate(16).unwrap();
+
+        assert_eq!(significant_bits.get_gray(), Some(12));
+        assert_eq!(significant_bits.get_alpha(), Some(1));
+        assert_eq!(significant_bits.get_red(), None);
+    }
+
+    #[test]
+    fn invalid_significant_bits() {
+        // Truecolor needs 3 bytes
+        let chunk = PNGChunk::new("sBIT", &[5, 6, 5, 8]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(SBIT::from_chunk_refs(&refs, 2).unwrap().validate(8).is_err());
+
+        let chunk = PNGChunk::new("sBIT", &[0]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(SBIT::from_chunk_refs(&refs, 0).unwrap().validate(8).is_err());
+
+        let chunk = PNGChunk::new("sBIT", &[9]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(SBIT::from_chunk_refs(&refs, 0).unwrap().validate(8).is_err());
+
+        let chunk = PNGChunk::new("sBIT", &[4]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert!(SBIT::from_chunk_refs(&refs, 5).unwrap().validate(8).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use crate::chunk::refs::ChunkRefs;
+
+/// A view over an sPLT chunk, which contains a suggested palette that viewers can use when the display
+/// cannot show the full range of colors in the image. A file can have more than one sPLT chunk, each
+/// with a different palette name.
+#[derive(Debug, Copy, Clone)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct SPLT<'a> {
+    /// Palette name. 1-79 bytes of Latin-1, without the null terminator.
+    name: &'a [u8],
+    /// Sample depth. 8 or 16.
+    sample_depth: u8,
+    /// The palette entries. 6 bytes each for a sample depth of 8, and 10 bytes each for a sample depth
+    /// of 16.
+    entries: &'a [u8],
+}
+
+/// A single suggested palette entry. For a sample depth of 8, the samples are in the range 0-255.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SuggestedPaletteEntry {
+    pub red: u16,
+    pub green: u16,
+    pub blue: u16,
+    pub alpha: u16,
+    /// Relative frequency of the color in the image, scaled so that the most frequent entry is 0xFFFF.
+    /// 0 means the frequency is unknown.
+    pub frequency: u16,
+}
+
+const MAX_NAME_LENGTH: usize = 79;
+
+impl<'a> SPLT<'a> {
+    /// Gets the palette name.
+    #[inline(always)]
+    pub fn get_name(&self) -> &'a [u8] {
+        self.name
+    }
+    /// Gets the sample depth, which is 8 or 16.
+    #[inline(always)]
+    pub fn get_sample_depth(&self) -> u8 {
+        self.sample_depth
+    }
+    /// Gets the size of each entry, based on the sample depth.
+    #[inline(always)]
+    fn get_entry_size(&self) -> usize {
+        entry_size(self.sample_depth)
+    }
+    /// Gets the number of palette entries.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len() / self.get_entry_size()
+    }
+    /// Returns true if the palette has no entries.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Iterates over the palette entries.
+    pub fn entries(&self) -> impl Iterator<Item = SuggestedPaletteEntry> + 'a {
+        let sample_depth = self.sample_depth;
+        self.entries
+            .chunks_exact(self.get_entry_size())
+            .map(move |entry| {
+                let sample = |i: usize| match sample_depth {
+                    8 => entry[i] as u16,
+                    _ => u16::from_be_bytes([entry[i * 2], entry[i * 2 + 1]]),
+                };
+                let frequency_start = entry.len() - 2;
+                SuggestedPaletteEntry {
+                    red: sample(0),
+                    green: sample(1),
+                    blue: sample(2),
+                    alpha: sample(3),
+                    frequency: u16::from_be_bytes([entry[frequency_start], entry[frequency_start + 1]]),
+                }
+            })
+    }
+}
+
+// Associated functions
+impl<'a> SPLT<'a> {
+    /// Parses the provided chunk as an sPLT chunk. Returns `None` if the chunk type is not "sPLT", if the
+    /// palette name is missing or too long, if the sample depth is not 8 or 16, or if the entries do not
+    /// evenly fit the entry size for the sample depth.
+    pub fn from_chunk_refs(chunk_refs: &ChunkRefs<'a>) -> Option<SPLT<'a>> {
+        if chunk_refs.get_chunk_type() != "sPLT" {
+            return None;
+        }
+
+        let data = chunk_refs.get_chunk_data();
+        let name_len = data.iter().position(|&b| b == 0)?;
+        if name_len == 0 || name_len > MAX_NAME_LENGTH {
+            return None;
+        }
+
+        let sample_depth = *data.get(name_len + 1)?;
+        if sample_depth != 8 && sample_depth != 16 {
+            return None;
+        }
+
+        let entries = &data[name_len + 2..];
+        if !entries.len().is_multiple_of(entry_size(sample_depth)) {
+            return None;
+        }
+
+        Some(SPLT {
+            name: &data[..name_len],
+            sample_depth,
+            entries,
+        })
+    }
+}
+
+/// Each entry is 4 samples (red, green, blue, alpha) and a 2-byte frequency.
+fn entry_size(sample_depth: u8) -> usize {
+    4 * (sample_depth as usize / 8) + 2
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::builder::PNGBuilder;
+    use crate::chunk::ty::ancillary::splt::{SuggestedPaletteEntry, SPLT};
+    use crate::chunk::PNGChunk;
+    #[cfg(feature = "std")]
+    use crate::PNGReader;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn suggested_palette() {
+        let mut deep_palette = b"deep\0\x10".to_vec();
+        deep_palette.extend([0xFF, 0xFF, 0, 0, 0, 0, 0xFF, 0xFF, 0, 1]);
+
+        let png_file = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]).unwrap())
+            .with_chunk(PNGChunk::new("sPLT", b"web\0\x08\xFF\x00\x00\xFF\x00\x02\x00\xFF\x00\xFF\x00\x01").unwrap())
+            .with_chunk(PNGChunk::new("sPLT", &deep_palette).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let web = png.suggested_palette("web").unwrap();
+        assert_eq!(web.get_sample_depth(), 8);
+        assert_eq!(web.len(), 2);
+        assert_eq!(
+            web.entries().nth(1),
+            Some(SuggestedPaletteEntry { red: 0, green: 255, blue: 0, alpha: 255, frequency: 1 })
+        );
+
+        let deep = png.suggested_palette("deep").unwrap();
+        assert_eq!(deep.get_name(), b"deep");
+        assert_eq!(deep.get_sample_depth(), 16);
+        assert_eq!(
+            deep.entries().next(),
+            Some(SuggestedPaletteEntry { red: 0xFFFF, green: 0, blue: 0, alpha: 0xFFFF, frequency: 1 })
+        );
+
+        assert!(png.suggested_palette("missing").is_none());
+    }
+
+    #[test]
+    fn splt_entries() {
+        let mut data = b"crab\0\x08".to_vec();
+        data.extend([0xF7, 0x4C, 0x00, 0xFF, 0x00, 0x10]);
+        data.extend([0x00, 0x00, 0x00, 0xFF, 0x00, 0x08]);
+        data.extend([0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00]);
+        let chunk = PNGChunk::new("sPLT", &data).unwrap();
+        let refs = chunk.as_chunk_refs();
+
+        let splt = SPLT::from_chunk_refs(&refs).unwrap();
+        assert_eq!(splt.get_name(), b"crab");
+        assert_eq!(splt.get_sample_depth(), 8);
+        assert_eq!(splt.entries().count(), 3);
+        assert_eq!(
+            splt.entries().next(),
+            Some(SuggestedPaletteEntry { red: 0xF7, green: 0x4C, blue: 0, alpha: 0xFF, frequency: 0x10 })
+        );
+
+        // One byte short of a full entry.
+        let chunk = PNGChunk::new("sPLT", &data[..data.len() - 1]).unwrap();
+        assert!(SPLT::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+        // The entries are 6 bytes each, which does not fit a sample depth of 16.
+        data[5] = 16;
+        let chunk = PNGChunk::new("sPLT", &data).unwrap();
+        assert!(SPLT::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+        data[5] = 4;
+        let chunk = PNGChunk::new("sPLT", &data).unwrap();
+        assert!(SPLT::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use alloc::format;
+use bytemuck::AnyBitPattern;
+
+/// If the sRGB chunk is present, the image samples conform to the sRGB color space and should be
+/// displayed using the specified rendering intent as defined by the International Color Consortium.
+#[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct SRGB {
+    /// Rendering intent. 0-3, see `RenderingIntent`.
+    rendering_intent: u8,
+}
+
+const SRGB_SIZE: usize = 1;
+const _: () = assert!(core::mem::size_of::<SRGB>() == SRGB_SIZE);
+
+/// The rendering intents defined by the International Color Consortium.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RenderingIntent {
+    /// For images preferring good adaptation to the output device gamut at the expense of colorimetric
+    /// accuracy, such as photographs.
+    Perceptual = 0,
+    /// For images requiring color appearance matching (relative to the output device white point), such
+    /// as logos.
+    RelativeColorimetric = 1,
+    /// For images preferring preservation of saturation at the expense of hue and lightness, such as
+    /// charts and graphs.
+    Saturation = 2,
+    /// For images requiring preservation of absolute colorimetry, such as previews of images destined
+    /// for a different output device (proofs).
+    AbsoluteColorimetric = 3,
+}
+
+impl RenderingIntent {
+    /// Gets the rendering intent for the provided value. Returns `None` if the value is greater than 3.
+    pub fn from_u8(value: u8) -> Option<RenderingIntent> {
+        match value {
+            0 => Some(RenderingIntent::Perceptual),
+            1 => Some(RenderingIntent::RelativeColorimetric),
+            2 => Some(RenderingIntent::Saturation),
+            3 => Some(RenderingIntent::AbsoluteColorimetric),
+            _ => None,
+        }
+    }
+}
+
+impl SRGB {
+    /// Checks that the rendering intent is 0-3.
+    pub fn validate(&self) -> Result<(), PngError> {
+        if self.get_intent().is_none() {
+            return Err(PngError::InvalidData(
+                format!("Invalid rendering intent. Must be 0-3. rendering intent: {}",
+                        self.rendering_intent,
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the rendering intent field value
+    #[inline(always)]
+    pub fn get_rendering_intent(&self) -> u8 {
+        self.rendering_intent
+    }
+    /// Gets the rendering intent. Returns `None` if the rendering intent is greater than 3.
+    #[inline(always)]
+    pub fn get_intent(&self) -> Option<RenderingIntent> {
+        RenderingIntent::from_u8(self.rendering_intent)
+    }
+}
+
+// Associated functions
+impl SRGB {
+    /// Creates a new sRGB `PNGChunk` with the provided rendering intent.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(intent: RenderingIntent) -> Result<PNGChunk, PngError> {
+        PNGChunk::new("sRGB", &[intent as u8])
+    }
+    /// Provides a reference to a SRGB provided the chunk_type matches "sRGB" and the size of the data
+    /// matches the size of SRGB.
+    pub fn from_chunk_refs<'a>(chunk_refs: &'a ChunkRefs<'a>) -> Option<&'a SRGB> {
+        if chunk_refs.get_chunk_type() != "sRGB" {
+            return None;
+        }
+
+        // Checks the size and alignment, instead of casting the pointer.
+        bytemuck::try_from_bytes(chunk_refs.get_chunk_data()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::srgb::{RenderingIntent, SRGB};
+    use crate::chunk::PNGChunk;
+    #[cfg(feature = "std")]
+    use crate::PNGReader;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_rendering_intent() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let srgb_chunk = png.get_chunk_of_type("sRGB").unwrap();
+        let srgb = SRGB::from_chunk_refs(&srgb_chunk).unwrap();
+        srgb.validate().unwrap();
+
+        assert_eq!(srgb.get_rendering_intent(), 0);
+        assert_eq!(srgb.get_intent(), Some(RenderingIntent::Perceptual));
+    }
+
+    #[test]
+    fn rendering_intent_round_trip() {
+        let chunk = SRGB::new(RenderingIntent::AbsoluteColorimetric).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let srgb = SRGB::from_chunk_refs(&refs).unwrap();
+
+        assert_eq!(srgb.get_rendering_intent(), 3);
+        assert_eq!(srgb.get_intent(), Some(RenderingIntent::AbsoluteColorimetric));
+    }
+
+    #[test]
+    fn invalid_rendering_intent() {
+        let chunk = PNGChunk::new("sRGB", &[4]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let srgb = SRGB::from_chunk_refs(&refs).unwrap();
+        assert!(srgb.validate().is_err());
+        assert!(srgb.get_intent().is_none());
+
+        let chunk = PNGChunk::new("sRGB", &[0, 0]).unwrap();
+        assert!(SRGB::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+    }
+}
@@ -0,0 +1,223 @@
+use alloc::borrow::Cow;
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use alloc::format;
+use alloc::vec::Vec;
+
+/// Textual information that the encoder wishes to record with the image. The keyword indicates the
+/// type of information represented by the text string, and the text is interpreted according to the
+/// ISO/IEC 8859-1 (Latin-1) character set.
+pub struct TEXt<'a> {
+    /// Keyword. 1-79 bytes, printable Latin-1 characters and spaces.
+    keyword: &'a [u8],
+    /// Text. 0 or more bytes of Latin-1 text. There is no null terminator.
+    text: &'a [u8],
+}
+
+const MAX_KEYWORD_LENGTH: usize = 79;
+
+impl<'a> TEXt<'a> {
+    /// Gets the keyword, transcoded from Latin-1. Fails if the keyword does not follow the restrictions
+    /// in `validate_keyword`.
+    pub fn get_keyword(&self) -> Result<Cow<'a, str>, PngError> {
+        let keyword = decode_latin1(self.keyword);
+        Self::validate_keyword(&keyword)?;
+
+        Ok(keyword)
+    }
+    /// Gets the raw keyword bytes.
+    #[inline(always)]
+    pub fn get_keyword_bytes(&self) -> &'a [u8] {
+        self.keyword
+    }
+    /// Gets the text, transcoded from Latin-1. Text that is plain ASCII is borrowed from the chunk.
+    pub fn get_text_latin1(&self) -> Cow<'a, str> {
+        decode_latin1(self.text)
+    }
+    /// Gets the raw text bytes.
+    #[inline(always)]
+    pub fn get_text_bytes(&self) -> &'a [u8] {
+        self.text
+    }
+}
+
+// Associated functions
+impl<'a> TEXt<'a> {
+    /// Splits the data of a tEXt chunk into its keyword and text on the first null byte. Returns `None`
+    /// if the chunk type is not "tEXt", if there is no null separator, or if the keyword is empty or
+    /// longer than 79 bytes.
+    pub fn from_chunk_refs(chunk_refs: &ChunkRefs<'a>) -> Option<TEXt<'a>> {
+        if chunk_refs.get_chunk_type() != "tEXt" {
+            return None;
+        }
+
+        let data = chunk_refs.get_chunk_data();
+        let null = data.iter().position(|&b| b == 0)?;
+        if null == 0 || null > MAX_KEYWORD_LENGTH {
+            return None;
+        }
+
+        Some(TEXt {
+            keyword: &data[..null],
+            text: &data[null + 1..],
+        })
+    }
+    /// Creates a new tEXt `PNGChunk` from the provided keyword and text. The keyword is checked with
+    /// `validate_keyword`, and both the keyword and text must only contain Latin-1 characters.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(keyword: &str, text: &str) -> Result<PNGChunk, PngError> {
+        Self::validate_keyword(keyword)?;
+
+        let mut data = encode_latin1(keyword)?;
+        data.push(0);
+        data.extend(encode_latin1(text)?);
+
+        PNGChunk::new("tEXt", &data)
+    }
+    /// Checks that the keyword follows the restrictions the spec places on tEXt and iTXt keywords.
+    /// Keywords must be 1-79 characters long, may only contain printable Latin-1 characters (32-126
+    /// and 161-255), and may not have leading, trailing, or consecutive spaces.
+    pub fn validate_keyword(keyword: &str) -> Result<(), PngError> {
+        let len = keyword.chars().count();
+        if len == 0 || len > MAX_KEYWORD_LENGTH {
+            return Err(PngError::InvalidData(
+                format!("Invalid keyword length. Must be 1-{MAX_KEYWORD_LENGTH} characters. keyword length: {len}"),
+            ));
+        }
+
+        if let Some(chr) = keyword.chars().find(|&chr| !is_printable_latin1(chr)) {
+            return Err(PngError::InvalidData(
+                format!("Keyword contains invalid character. {:?} (U+{:04X})", chr, chr as u32),
+            ));
+        }
+
+        if keyword.starts_with(' ') || keyword.ends_with(' ') {
+            return Err(PngError::InvalidData(
+                format!("Keyword cannot have leading or trailing spaces. keyword: {keyword:?}"),
+            ));
+        }
+
+        if keyword.contains("  ") {
+            return Err(PngError::InvalidData(
+                format!("Keyword cannot have consecutive spaces. keyword: {keyword:?}"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Every Latin-1 byte maps directly to the unicode code point with the same value. ASCII is the same in
+/// Latin-1 and UTF-8, so ASCII buffers are borrowed instead of copied.
+pub(crate) fn decode_latin1(buffer: &[u8]) -> Cow<'_, str> {
+    match core::str::from_utf8(buffer) {
+        Ok(string) if buffer.is_ascii() => Cow::Borrowed(string),
+        _ => Cow::Owned(buffer.iter().map(|&b| b as char).collect()),
+    }
+}
+/// Printable Latin-1 characters are 32-126 and 161-255.
+fn is_printable_latin1(chr: char) -> bool {
+    matches!(chr as u32, 32..=126 | 161..=255)
+}
+/// Encodes the provided string as Latin-1. Fails if the string has any characters outside of Latin-1.
+fn encode_latin1(string: &str) -> Result<Vec<u8>, PngError> {
+    string
+        .chars()
+        .map(|chr| {
+            u8::try_from(chr as u32).map_err(|_| {
+                PngError::InvalidData(
+                    format!("Character is not Latin-1. {:?} (U+{:04X})", chr, chr as u32),
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::text::TEXt;
+    use crate::chunk::PNGChunk;
+    use alloc::vec;
+
+    #[test]
+    fn validate_keyword() {
+        TEXt::validate_keyword("Title").unwrap();
+        TEXt::validate_keyword("Creation Time").unwrap();
+        TEXt::validate_keyword("Caf\u{E9}").unwrap();
+        TEXt::validate_keyword(&"k".repeat(79)).unwrap();
+    }
+
+    #[test]
+    fn validate_keyword_length() {
+        assert!(TEXt::validate_keyword("").is_err());
+        assert!(TEXt::validate_keyword(&"k".repeat(80)).is_err());
+    }
+
+    #[test]
+    fn validate_keyword_characters() {
+        assert!(TEXt::validate_keyword("Tab\tbed").is_err());
+        assert!(TEXt::validate_keyword("Null\0").is_err());
+        assert!(TEXt::validate_keyword("Del\u{7F}").is_err());
+        assert!(TEXt::validate_keyword("No\u{A0}break").is_err());
+        assert!(TEXt::validate_keyword("Check \u{2713}").is_err());
+    }
+
+    #[test]
+    fn validate_keyword_spaces() {
+        assert!(TEXt::validate_keyword(" Title").is_err());
+        assert!(TEXt::validate_keyword("Title ").is_err());
+        assert!(TEXt::validate_keyword("Creation  Time").is_err());
+    }
+
+    #[test]
+    fn new_text_chunk() {
+        let chunk = TEXt::new("Author", "Caf\u{E9}").unwrap();
+        assert_eq!(chunk.get_chunk_type(), "tEXt");
+        assert_eq!(chunk.get_chunk_data(), b"Author\0Caf\xE9");
+
+        assert!(TEXt::new("Author ", "Ferris").is_err());
+        assert!(TEXt::new("Author", "Check \u{2713}").is_err());
+    }
+
+    #[test]
+    fn read_text_chunk() {
+        let chunk = TEXt::new("Author", "Caf\u{E9}").unwrap();
+        let refs = chunk.as_chunk_refs();
+        let text = TEXt::from_chunk_refs(&refs).unwrap();
+
+        assert_eq!(text.get_keyword().unwrap(), "Author");
+        assert_eq!(text.get_text_latin1(), "Caf\u{E9}");
+    }
+
+    #[test]
+    fn read_empty_text() {
+        // The null separator is the last byte, so the text is empty.
+        let chunk = PNGChunk::new("tEXt", b"Comment\0").unwrap();
+        let refs = chunk.as_chunk_refs();
+        let text = TEXt::from_chunk_refs(&refs).unwrap();
+
+        assert_eq!(text.get_keyword().unwrap(), "Comment");
+        assert_eq!(text.get_text_latin1(), "");
+    }
+
+    #[test]
+    fn read_invalid_text_chunk() {
+        let no_separator = PNGChunk::new("tEXt", b"Comment").unwrap();
+        assert!(TEXt::from_chunk_refs(&no_separator.as_chunk_refs()).is_none());
+
+        let empty_keyword = PNGChunk::new("tEXt", b"\0text").unwrap();
+        assert!(TEXt::from_chunk_refs(&empty_keyword.as_chunk_refs()).is_none());
+
+        let mut long_keyword = vec![b'k'; 80];
+        long_keyword.extend(b"\0text");
+        let long_keyword = PNGChunk::new("tEXt", &long_keyword).unwrap();
+        assert!(TEXt::from_chunk_refs(&long_keyword.as_chunk_refs()).is_none());
+
+        let unprintable_keyword = PNGChunk::new("tEXt", b"Tab\tbed\0text").unwrap();
+        let refs = unprintable_keyword.as_chunk_refs();
+        assert!(TEXt::from_chunk_refs(&refs).unwrap().get_keyword().is_err());
+
+        assert!(TEXt::new("Null\0keyword", "text").is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use alloc::format;
+use alloc::vec::Vec;
+use bytemuck::AnyBitPattern;
+
+/// The tIME chunk gives the time of the last image modification (not the time of initial image
+/// creation). Universal Time (UTC, also called GMT) should be specified rather than local time.
+#[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct TIME {
+    /// Year. 2-byte integer, complete; for example, 1995, not 95.
+    year: [u8; 2],
+    /// Month. 1-12
+    month: u8,
+    /// Day. 1-31
+    day: u8,
+    /// Hour. 0-23
+    hour: u8,
+    /// Minute. 0-59
+    minute: u8,
+    /// Second. 0-60 (yes, 60, for leap seconds; not 61, a common error)
+    second: u8,
+}
+
+const TIME_SIZE: usize = 7;
+const _: () = assert!(core::mem::size_of::<TIME>() == TIME_SIZE);
+
+impl TIME {
+    /// Checks that each field is within its valid range. Month 1-12, day 1-31, hour 0-23, minute 0-59
+    /// and second 0-60.
+    pub fn validate(&self) -> Result<(), PngError> {
+        Self::validate_field("month", self.month, 1, 12)?;
+        Self::validate_field("day", self.day, 1, 31)?;
+        Self::validate_field("hour", self.hour, 0, 23)?;
+        Self::validate_field("minute", self.minute, 0, 59)?;
+        Self::validate_field("second", self.second, 0, 60)?;
+
+        Ok(())
+    }
+    /// Gets the year field value
+    #[inline(always)]
+    pub fn get_year(&self) -> u16 {
+        u16::from_be_bytes(self.year)
+    }
+    /// Gets the month field value
+    #[inline(always)]
+    pub fn get_month(&self) -> u8 {
+        self.month
+    }
+    /// Gets the day field value
+    #[inline(always)]
+    pub fn get_day(&self) -> u8 {
+        self.day
+    }
+    /// Gets the hour field value
+    #[inline(always)]
+    pub fn get_hour(&self) -> u8 {
+        self.hour
+    }
+    /// Gets the minute field value
+    #[inline(always)]
+    pub fn get_minute(&self) -> u8 {
+        self.minute
+    }
+    /// Gets the second field value
+    #[inline(always)]
+    pub fn get_second(&self) -> u8 {
+        self.second
+    }
+    /// Converts the time to seconds since the Unix epoch. The time is treated as UTC, as the spec
+    /// requires. Returns `None` if any field is out of range, or if the day does not exist in the month,
+    /// like February 29th in a year that is not a leap year. Unix time does not count leap seconds, so a
+    /// second value of 60 gives the same timestamp as the first second of the next minute.
+    pub fn to_unix_timestamp(&self) -> Option<i64> {
+        self.validate().ok()?;
+
+        let year = self.get_year() as i64;
+        let month = self.month as i64;
+        if self.day > days_in_month(year, self.month) {
+            return None;
+        }
+
+        // Count days from 0000-03-01, so that the leap day is the last day of the year.
+        let shifted_year = if month <= 2 { year - 1 } else { year };
+        let era = shifted_year.div_euclid(400);
+        let year_of_era = shifted_year.rem_euclid(400);
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        // 719468 is the number of days from 0000-03-01 to 1970-01-01
+        let days = era * 146097 + day_of_era - 719468;
+
+        Some(days * 86400 + self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64)
+    }
+}
+
+/// Returns true if the year is a leap year in the Gregorian calendar.
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+/// Gets the number of days in the month. The month must be 1-12.
+fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Associated functions
+impl TIME {
+    /// Creates a new tIME `PNGChunk` with the provided date and time, in UTC. Fails if any of the fields
+    /// are out of range.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Result<PNGChunk, PngError> {
+        let time = TIME {
+            year: year.to_be_bytes(),
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        };
+        time.validate()?;
+
+        let mut data = Vec::with_capacity(TIME_SIZE);
+        data.extend(time.year);
+        data.extend([time.month, time.day, time.hour, time.minute, time.second]);
+
+        PNGChunk::new("tIME", &data)
+    }
+    /// Provides a reference to a TIME provided the chunk_type matches "tIME" and the size of the data
+    /// matches the size of TIME.
+    pub fn from_chunk_refs<'a>(chunk_refs: &'a ChunkRefs<'a>) -> Option<&'a TIME> {
+        if chunk_refs.get_chunk_type() != "tIME" {
+            return None;
+        }
+
+        // Checks the size and alignment, instead of casting the pointer.
+        bytemuck::try_from_bytes(chunk_refs.get_chunk_data()).ok()
+    }
+    /// Checks that the provided field value is within `min..=max`.
+    fn validate_field(name: &str, value: u8, min: u8, max: u8) -> Result<(), PngError> {
+        if !(min..=max).contains(&value) {
+            return Err(PngError::InvalidData(
+                format!("Invalid {name}. Must be {min}-{max}. {name}: {value}"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ty::ancillary::time::TIME;
+    use crate::chunk::PNGChunk;
+
+    #[test]
+    fn time_round_trip() {
+        let chunk = TIME::new(2024, 2, 29, 23, 59, 60).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let time = TIME::from_chunk_refs(&refs).unwrap();
+        time.validate().unwrap();
+
+        assert_eq!(time.get_year(), 2024);
+        assert_eq!(time.get_month(), 2);
+        assert_eq!(time.get_day(), 29);
+        assert_eq!(time.get_hour(), 23);
+        assert_eq!(time.get_minute(), 59);
+        assert_eq!(time.get_second(), 60);
+    }
+
+    #[test]
+    fn invalid_time() {
+        assert!(TIME::new(2024, 0, 1, 0, 0, 0).is_err());
+        assert!(TIME::new(2024, 13, 1, 0, 0, 0).is_err());
+        assert!(TIME::new(2024, 1, 32, 0, 0, 0).is_err());
+        assert!(TIME::new(2024, 1, 1, 24, 0, 0).is_err());
+        assert!(TIME::new(2024, 1, 1, 0, 60, 0).is_err());
+        assert!(TIME::new(2024, 1, 1, 0, 0, 61).is_err());
+
+        let chunk = PNGChunk::new("tIME", &[7, 232, 1, 1, 0, 0]).unwrap();
+        assert!(TIME::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+    }
+
+    #[test]
+    fn unix_timestamp() {
+        let timestamp = |year, month, day, hour, minute, second| {
+            let chunk = TIME::new(year, month, day, hour, minute, second).unwrap();
+            let refs = chunk.as_chunk_refs();
+            TIME::from_chunk_refs(&refs).unwrap().to_unix_timestamp()
+        };
+
+        assert_eq!(timestamp(1970, 1, 1, 0, 0, 0), Some(0));
+        assert_eq!(timestamp(2000, 2, 29, 12, 0, 0), Some(951825600));
+        assert_eq!(timestamp(2024, 12, 31, 23, 59, 59), Some(1735689599));
+        assert_eq!(timestamp(1969, 12, 31, 23, 59, 59), Some(-1));
+        assert_eq!(timestamp(1900, 3, 1, 0, 0, 0), Some(-2203891200));
+
+        assert_eq!(timestamp(2023, 2, 29, 0, 0, 0), None);
+        assert_eq!(timestamp(1900, 2, 29, 0, 0, 0), None);
+        assert_eq!(timestamp(2024, 4, 31, 0, 0, 0), None);
+
+        let chunk = PNGChunk::new("tIME", &[7, 232, 13, 1, 0, 0, 0]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert_eq!(TIME::from_chunk_refs(&refs).unwrap().to_unix_timestamp(), None);
+    }
+}
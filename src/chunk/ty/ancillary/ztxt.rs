@@ -0,0 +1,230 @@
+use alloc::borrow::Cow;
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::ty::ancillary::text::{decode_latin1, TEXt};
+#[cfg(feature = "text-decode")]
+use crate::consts::MAX_DECOMPRESSED_TEXT_LENGTH;
+use alloc::format;
+#[cfg(feature = "text-decode")]
+use alloc::string::String;
+#[cfg(feature = "text-decode")]
+use alloc::vec::Vec;
+#[cfg(feature = "text-decode")]
+use alloc::vec;
+
+/// The zTXt chunk contains textual data, just as tEXt does. However, zTXt takes advantage of compression.
+/// zTXt and tEXt chunks are semantically equivalent, but zTXt is recommended for storing large blocks
+/// of text.
+pub struct ZTXt<'a> {
+    /// Keyword. 1-79 bytes, printable Latin-1 characters and spaces.
+    keyword: &'a [u8],
+    /// Compression method. 0 (zlib datastream with deflate compression) is the only method defined.
+    compression_method: u8,
+    /// Compressed text. A zlib datastream which decompresses to Latin-1 text.
+    compressed_data: &'a [u8],
+}
+
+const MAX_KEYWORD_LENGTH: usize = 79;
+/// The only compression method defined by the spec. zlib datastream with deflate compression.
+pub const COMPRESSION_METHOD_DEFLATE: u8 = 0;
+
+impl<'a> ZTXt<'a> {
+    /// Checks that the compression method is 0, which is the only compression method defined by the spec.
+    pub fn validate(&self) -> Result<(), PngError> {
+        if self.compression_method != COMPRESSION_METHOD_DEFLATE {
+            return Err(PngError::InvalidData(
+                format!("Invalid compression method. Must be 0. compression method: {}",
+                        self.compression_method,
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the keyword, transcoded from Latin-1. Fails if the keyword does not follow the restrictions
+    /// in `TEXt::validate_keyword`.
+    pub fn get_keyword(&self) -> Result<Cow<'a, str>, PngError> {
+        let keyword = decode_latin1(self.keyword);
+        TEXt::validate_keyword(&keyword)?;
+
+        Ok(keyword)
+    }
+    /// Gets the raw keyword bytes.
+    #[inline(always)]
+    pub fn get_keyword_bytes(&self) -> &'a [u8] {
+        self.keyword
+    }
+    /// Gets the compression method field value
+    #[inline(always)]
+    pub fn get_compression_method(&self) -> u8 {
+        self.compression_method
+    }
+    /// Gets the compressed zlib datastream, without decompressing it.
+    #[inline(always)]
+    pub fn get_compressed_data(&self) -> &'a [u8] {
+        self.compressed_data
+    }
+    /// Decompresses the text and transcodes it from Latin-1. Fails if the compression method is not 0,
+    /// if the compressed data is not a valid zlib datastream, or if the text is longer than
+    /// `MAX_DECOMPRESSED_TEXT_LENGTH` bytes once decompressed.
+    #[cfg(feature = "text-decode")]
+    pub fn get_text(&self) -> Result<String, PngError> {
+        self.validate()?;
+        let text = inflate(self.compressed_data)?;
+
+        Ok(decode_latin1(&text).into_owned())
+    }
+}
+
+// Associated functions
+impl<'a> ZTXt<'a> {
+    /// Splits the data of a zTXt chunk into its keyword, compression method and compressed text. Returns
+    /// `None` if the chunk type is not "zTXt", if there is no null separator, if the keyword is empty or
+    /// longer than 79 bytes, or if the compression method byte is missing.
+    pub fn from_chunk_refs(chunk_refs: &ChunkRefs<'a>) -> Option<ZTXt<'a>> {
+        if chunk_refs.get_chunk_type() != "zTXt" {
+            return None;
+        }
+
+        let data = chunk_refs.get_chunk_data();
+        let null = data.iter().position(|&b| b == 0)?;
+        if null == 0 || null > MAX_KEYWORD_LENGTH {
+            return None;
+        }
+
+        let compression_method = *data.get(null + 1)?;
+
+        Some(ZTXt {
+            keyword: &data[..null],
+            compression_method,
+            compressed_data: &data[null + 2..],
+        })
+    }
+}
+
+/// Decompresses a zlib datastream. Returns an error if the datastream is malformed or truncated, or if it
+/// decompresses to more than `MAX_DECOMPRESSED_TEXT_LENGTH` bytes.
+#[cfg(feature = "text-decode")]
+pub(crate) fn inflate(compressed: &[u8]) -> Result<Vec<u8>, PngError> {
+    use std::io::Read;
+
+    let mut decompressed = vec![];
+    // Read one byte past the limit, so that text that is exactly at the limit is still allowed.
+    flate2::read::ZlibDecoder::new(compressed)
+        .take(MAX_DECOMPRESSED_TEXT_LENGTH as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| PngError::InvalidData(
+            format!("Could not decompress zlib datastream. {e}"),
+        ))?;
+    if decompressed.len() > MAX_DECOMPRESSED_TEXT_LENGTH {
+        return Err(PngError::InvalidData(
+            format!("Decompressed text is too long. Max: 0x{MAX_DECOMPRESSED_TEXT_LENGTH:X}"),
+        ));
+    }
+
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::builder::PNGBuilder;
+    use crate::chunk::ty::ancillary::ztxt::ZTXt;
+    use crate::chunk::PNGChunk;
+    #[cfg(feature = "std")]
+    use crate::PNGReader;
+
+    /// "Made with love for the crab", compressed with zlib at level 9.
+    const COMPRESSED_TEXT: [u8; 35] = [
+        120, 218, 243, 77, 76, 73, 85, 40, 207, 44, 201, 80, 200, 201, 47, 75, 85, 72, 203, 47, 82, 40,
+        201, 72, 85, 72, 46, 74, 76, 2, 0, 134, 216, 9, 170,
+    ];
+
+    fn ztxt_chunk(compression_method: u8, compressed: &[u8]) -> PNGChunk {
+        let mut data = b"Comment\0".to_vec();
+        data.push(compression_method);
+        data.extend(compressed);
+
+        PNGChunk::new("zTXt", &data).unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_compressed_text() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_chunk(ztxt_chunk(0, &COMPRESSED_TEXT))
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        let ztxt_chunk = new_png.get_chunk_of_type("zTXt").unwrap();
+        let ztxt = ZTXt::from_chunk_refs(&ztxt_chunk).unwrap();
+        ztxt.validate().unwrap();
+
+        assert_eq!(ztxt.get_keyword().unwrap(), "Comment");
+        assert_eq!(ztxt.get_compressed_data(), COMPRESSED_TEXT);
+        #[cfg(feature = "text-decode")]
+        assert_eq!(ztxt.get_text().unwrap(), "Made with love for the crab");
+    }
+
+    #[test]
+    fn invalid_compressed_text() {
+        let missing_method = PNGChunk::new("zTXt", b"Comment\0").unwrap();
+        assert!(ZTXt::from_chunk_refs(&missing_method.as_chunk_refs()).is_none());
+
+        let chunk = ztxt_chunk(1, &COMPRESSED_TEXT);
+        let refs = chunk.as_chunk_refs();
+        let ztxt = ZTXt::from_chunk_refs(&refs).unwrap();
+        assert!(ztxt.validate().is_err());
+        #[cfg(feature = "text-decode")]
+        assert!(ztxt.get_text().is_err());
+
+        let chunk = ztxt_chunk(0, &COMPRESSED_TEXT[..20]);
+        let refs = chunk.as_chunk_refs();
+        let ztxt = ZTXt::from_chunk_refs(&refs).unwrap();
+        ztxt.validate().unwrap();
+        #[cfg(feature = "text-decode")]
+        assert!(ztxt.get_text().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "text-decode")]
+    fn decompression_limit() {
+        use crate::chunk::ty::ancillary::itxt::ITXt;
+        use crate::consts::MAX_DECOMPRESSED_TEXT_LENGTH;
+        use crate::error::PngError;
+        use flate2::write::ZlibEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let compress = |len: usize| {
+            let mut encoder = ZlibEncoder::new(vec![], Compression::best());
+            encoder.write_all(&vec![b'a'; len]).unwrap();
+            encoder.finish().unwrap()
+        };
+
+        let chunk = ztxt_chunk(0, &compress(MAX_DECOMPRESSED_TEXT_LENGTH));
+        let refs = chunk.as_chunk_refs();
+        let ztxt = ZTXt::from_chunk_refs(&refs).unwrap();
+        assert_eq!(ztxt.get_text().unwrap().len(), MAX_DECOMPRESSED_TEXT_LENGTH);
+
+        // A run of one byte compresses to about a thousandth of its size.
+        let bomb = compress(MAX_DECOMPRESSED_TEXT_LENGTH + 1);
+        let chunk = ztxt_chunk(0, &bomb);
+        let refs = chunk.as_chunk_refs();
+        let ztxt = ZTXt::from_chunk_refs(&refs).unwrap();
+        let err = ztxt.get_text().unwrap_err();
+        assert!(matches!(&err, PngError::InvalidData(msg) if msg.contains("too long")), "{err}");
+
+        let mut data = b"Comment\0\x01\0\0\0".to_vec();
+        data.extend(&bomb);
+        let chunk = PNGChunk::new("iTXt", &data).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let itxt = ITXt::from_chunk_refs(&refs).unwrap();
+        assert!(matches!(itxt.get_text(), Err(PngError::InvalidData(_))));
+    }
+}
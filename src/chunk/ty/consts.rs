@@ -1,5 +1,5 @@
 #![allow(unused)]
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
 
 /// 5th bit mask
 pub(crate) const BIT_FIVE_MASK: u8 = 1 << 5;
@@ -1,9 +1,13 @@
 #![allow(unused)]
 
-use std::io::{Error, ErrorKind};
 use crate::chunk::refs::ChunkRefs;
+use crate::chunk::PNGChunk;
+use crate::error::PngError;
+use bytemuck::AnyBitPattern;
+use alloc::format;
 
 #[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
 #[allow(clippy::upper_case_acronyms)]
 pub struct IHDR {
     /// Width. 4-byte integer. Zero is an invalid value. The maximum value is 2^31 in order to accommodate
@@ -17,15 +21,27 @@ pub struct IHDR {
 }
 
 const IHDR_SIZE: usize = 13;
-const _: () = assert!(std::mem::size_of::<IHDR>() == IHDR_SIZE);
+const _: () = assert!(core::mem::size_of::<IHDR>() == IHDR_SIZE);
+// Chunk data has no alignment guarantee, so `from_chunk_refs` relies on IHDR only being made of bytes.
+const _: () = assert!(core::mem::align_of::<IHDR>() == 1);
+
+/// Starting column, starting row, column step and row step of each of the seven Adam7 passes.
+const ADAM7_PASSES: [(u32, u32, u32, u32); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
 
 impl IHDR {
     /// Checks that the dimensions of the IHDR are correct
-    pub fn validate_dimensions(&self) -> std::io::Result<()> {
+    pub fn validate_dimensions(&self) -> Result<(), PngError> {
         let width = self.get_width();
         if !Self::is_valid_dimension(width) {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
+            return Err(PngError::InvalidIhdr(
                 format!("Invalid width. Dimensions cannot be less than 1 pixel. width: {}",
                         width,
                 ),
@@ -33,8 +49,7 @@ impl IHDR {
         }
         let height = self.get_height();
         if !Self::is_valid_dimension(height) {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
+            return Err(PngError::InvalidIhdr(
                 format!("Invalid height. Dimensions cannot be less than 1 pixel. height: {}",
                         height,
                 ),
@@ -44,7 +59,7 @@ impl IHDR {
         Ok(())
     }
     /// Validates both the dimensions and the details structure.
-    pub fn validate(&self) -> std::io::Result<()> {
+    pub fn validate(&self) -> Result<(), PngError> {
         self.validate_dimensions()?;
         self.details.validate()
     }
@@ -74,13 +89,40 @@ impl IHDR {
         self.height = height.to_be_bytes();
         true
     }
+    /// Gets the bit depth, color type, compression method, filter method, and interlace method
+    #[inline(always)]
+    pub fn get_details(&self) -> &IHDRDetails {
+        &self.details
+    }
+    /// Serializes the IHDR into the 13 bytes of IHDR chunk data.
+    pub fn to_bytes(&self) -> [u8; IHDR_SIZE] {
+        let details = self.get_details();
+        let mut bytes = [0; IHDR_SIZE];
+        bytes[..4].copy_from_slice(&self.get_width().to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.get_height().to_be_bytes());
+        bytes[8..].copy_from_slice(&[
+            details.get_bit_depth(),
+            details.get_color_type(),
+            details.get_compression_method(),
+            details.get_filter_method(),
+            details.get_interlace_method(),
+        ]);
+
+        bytes
+    }
+    /// Validates the IHDR and serializes it into a new IHDR `PNGChunk`.
+    pub fn to_chunk(&self) -> Result<PNGChunk, PngError> {
+        self.validate()?;
+
+        PNGChunk::new("IHDR", &self.to_bytes())
+    }
 }
 
 // Associated functions
 impl IHDR {
     /// Provides a new IHDR with the provided width, height and IHDRDetails. Only validates the dimensions
     /// of the IHDR, so the user must pass in a valid IHDRDetails struct.
-    pub fn new(width: i32, height: i32, details: IHDRDetails) -> std::io::Result<Self> {
+    pub fn new(width: i32, height: i32, details: IHDRDetails) -> Result<Self, PngError> {
         let header = IHDR {
             width: width.to_be_bytes(),
             height: height.to_be_bytes(),
@@ -97,11 +139,23 @@ impl IHDR {
         if chunk_refs.get_chunk_type() != "IHDR" {
             return None;
         }
-        if chunk_refs.get_chunk_data().len() != std::mem::size_of::<IHDR>() {
+
+        // Checks the size and alignment, instead of casting the pointer.
+        bytemuck::try_from_bytes(chunk_refs.get_chunk_data()).ok()
+    }
+    /// Gets the width and height of each of the seven Adam7 passes, if the image is interlaced. Passes can
+    /// be empty for images smaller than 8x8 pixels, in which case the pass has no scanlines in the image
+    /// data. Returns `None` if the image is not interlaced.
+    pub fn adam7_pass_dimensions(&self) -> Option<[(u32, u32); 7]> {
+        if self.details.get_interlace_method() != 1 {
             return None;
         }
 
-        Some(unsafe { &*(chunk_refs.get_chunk_data().as_ptr() as *const IHDR) })
+        let width = self.get_width() as u32;
+        let height = self.get_height() as u32;
+        let pass_len = |len: u32, start: u32, step: u32| len.saturating_sub(start).div_ceil(step);
+
+        Some(ADAM7_PASSES.map(|(x, y, dx, dy)| (pass_len(width, x, dx), pass_len(height, y, dy))))
     }
     /// Check if the provided dimension (width or height) is a valid dimension value. Value must be
     /// greater than 0
@@ -112,6 +166,7 @@ impl IHDR {
 
 /// bit depth, color type, compression method, filter method, and interlace method
 #[repr(C)]
+#[derive(Copy, Clone, AnyBitPattern)]
 pub struct IHDRDetails {
     /// Bit depth is a single-byte integer giving the number of bits per sample or per palette index
     /// (not per pixel). Valid values are 1, 2, 4, 8, and 16, although not all values are allowed for
@@ -161,8 +216,13 @@ pub struct IHDRDetails {
 }
 
 const IHDR_DETAILS_SIZE: usize = 5;
-const _: () = assert!(std::mem::size_of::<IHDRDetails>() == IHDR_DETAILS_SIZE);
+const _: () = assert!(core::mem::size_of::<IHDRDetails>() == IHDR_DETAILS_SIZE);
 const VALID_BIT_DEPTHS: [u8; 5] = [1, 2, 4, 8, 16];
+// Color type codes are sums of these values.
+const PALETTE_USED: u8 = 1;
+const COLOR_USED: u8 = 2;
+const ALPHA_USED: u8 = 4;
+const VALID_COLOR_TYPES: [u8; 5] = [0, 2, 3, 4, 6];
 const COLOR_TYPE_LOOKUP_TABLE: [&[u8]; 7] = [
     &VALID_BIT_DEPTHS,
     &[],
@@ -176,14 +236,13 @@ const COLOR_TYPE_LOOKUP_TABLE: [&[u8]; 7] = [
 impl IHDRDetails {
     /// Checks that the bit depth is valid for the color type option, and checks that the interlace method
     /// is a valid value.
-    pub fn validate(&self) -> std::io::Result<()> {
+    pub fn validate(&self) -> Result<(), PngError> {
         Self::is_valid_bit_depth(self.bit_depth)?;
 
         Self::is_valid_bit_depth_for_color_type(self.color_type, self.bit_depth)?;
 
         if self.compression_method != 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
+            return Err(PngError::InvalidIhdr(
                 format!("Invalid compression method. Must be 0. compression method: {}",
                         self.compression_method,
                 ),
@@ -191,8 +250,7 @@ impl IHDRDetails {
         }
 
         if self.filter_method != 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
+            return Err(PngError::InvalidIhdr(
                 format!("Invalid filter method. Must be 0. filter method: {}",
                         self.filter_method,
                 ),
@@ -221,7 +279,7 @@ impl IHDRDetails {
     }
     /// Sets the bit_depth field value. Can fail if the bit depth value is invalid, or the bit depth
     /// value is invalid for the current color type. If you want to change both, use `set_bit_depth_and_color_type`
-    pub fn set_bit_depth(&mut self, bit_depth: u8) -> std::io::Result<()> {
+    pub fn set_bit_depth(&mut self, bit_depth: u8) -> Result<(), PngError> {
         Self::is_valid_bit_depth(bit_depth)?;
         Self::is_valid_bit_depth_for_color_type(self.color_type, bit_depth)?;
 
@@ -235,7 +293,7 @@ impl IHDRDetails {
     }
     /// Sets the color_type field value. Can fail if the current bit depth value is invalid for the
     /// provided color type. If you want to change both, use `set_bit_depth_and_color_type`
-    pub fn set_color_type(&mut self, color_type: u8) -> std::io::Result<()> {
+    pub fn set_color_type(&mut self, color_type: u8) -> Result<(), PngError> {
         Self::is_valid_bit_depth_for_color_type(color_type, self.bit_depth)?;
 
         self.color_type = color_type;
@@ -243,7 +301,7 @@ impl IHDRDetails {
     }
     /// Sets the bit_depth and color_type field value. Can fail if the bit depth value is invalid, or the bit depth
     /// value is invalid for the provided color_type.
-    pub fn set_bit_depth_and_color_type(&mut self, color_type: u8, bit_depth: u8) -> std::io::Result<()> {
+    pub fn set_bit_depth_and_color_type(&mut self, color_type: u8, bit_depth: u8) -> Result<(), PngError> {
         Self::is_valid_bit_depth(bit_depth)?;
         Self::is_valid_bit_depth_for_color_type(color_type, bit_depth)?;
 
@@ -266,11 +324,21 @@ impl IHDRDetails {
         self.compression_method = compression_method;
         true
     }
+    /// Gets the compression method as a `CompressionMethod`.
+    #[inline(always)]
+    pub fn compression(&self) -> CompressionMethod {
+        self.compression_method.into()
+    }
     /// Gets the filter_method field value.
     #[inline(always)]
     pub fn get_filter_method(&self) -> u8 {
         self.filter_method
     }
+    /// Gets the filter method as a `FilterMethod`.
+    #[inline(always)]
+    pub fn filter(&self) -> FilterMethod {
+        self.filter_method.into()
+    }
     /// Sets the filter_method field value. This value must always be 0, in the current spec.
     #[must_use = "Setting will fail if filter method is not set to 0"]
     pub fn set_filter_method(&mut self, filter_method: u8) -> bool {
@@ -287,12 +355,50 @@ impl IHDRDetails {
     }
     /// Sets the interlace_method field value. This value must always be  0 (no interlace) or 1 (Adam7
     /// interlace), in the current spec.
-    pub fn set_interlace_method(&mut self, interlace_method: u8) -> std::io::Result<()>  {
+    pub fn set_interlace_method(&mut self, interlace_method: u8) -> Result<(), PngError>  {
         Self::is_valid_interlace_method(interlace_method)?;
 
         self.interlace_method = interlace_method;
         Ok(())
     }
+    /// Returns true if the color type has no color, meaning color type 0 (grayscale) or 4 (grayscale and
+    /// alpha).
+    #[inline(always)]
+    pub fn is_grayscale(&self) -> bool {
+        self.color_type & COLOR_USED == 0
+    }
+    /// Returns true if the color type has an alpha channel, meaning color type 4 or 6. Transparency from a
+    /// tRNS chunk is not counted.
+    #[inline(always)]
+    pub fn has_alpha(&self) -> bool {
+        self.color_type & ALPHA_USED != 0
+    }
+    /// Returns true if the color type is 3, where each pixel is an index into the PLTE chunk.
+    #[inline(always)]
+    pub fn uses_palette(&self) -> bool {
+        self.color_type & PALETTE_USED != 0
+    }
+    /// Gets the number of channels in each pixel for the color type. 1 for grayscale and palette index,
+    /// 2 for grayscale and alpha, 3 for RGB, and 4 for RGB and alpha.
+    pub fn channel_count(&self) -> u8 {
+        match self.color_type {
+            2 => 3,
+            4 => 2,
+            6 => 4,
+            _ => 1,
+        }
+    }
+    /// Gets the number of bits in each pixel, which is the channel count times the bit depth. For color
+    /// type 3, this is the size of the palette index, not the size of the palette entry.
+    pub fn bits_per_pixel(&self) -> u16 {
+        self.channel_count() as u16 * self.bit_depth as u16
+    }
+    /// Gets the length of a filtered scanline of the provided width in the image data, including the
+    /// filter type byte at the start of the scanline. Pixels are packed into bytes for bit depths lower
+    /// than 8, so the scanline is rounded up to a full byte.
+    pub fn bytes_per_scanline(&self, width: u32) -> u64 {
+        1 + (width as u64 * self.bits_per_pixel() as u64).div_ceil(8)
+    }
 }
 
 // Associated functions
@@ -304,7 +410,7 @@ impl IHDRDetails {
         compression_method: u8,
         filter_method: u8,
         interlace_method: u8,
-    ) -> std::io::Result<Self> {
+    ) -> Result<Self, PngError> {
         let details = IHDRDetails {
             bit_depth,
             color_type,
@@ -318,10 +424,9 @@ impl IHDRDetails {
         Ok(details)
     }
     /// Checks the given bit depth given is a valid bit depth. Valid bit depths : 1, 2, 4, 8, 16
-    fn is_valid_bit_depth(bit_depth: u8) -> std::io::Result<()> {
+    fn is_valid_bit_depth(bit_depth: u8) -> Result<(), PngError> {
         if !VALID_BIT_DEPTHS.contains(&bit_depth) {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
+            return Err(PngError::InvalidIhdr(
                 format!("Invalid bit depth: {}\n\
                 Valid values: {:?}",
                         bit_depth,
@@ -334,18 +439,33 @@ impl IHDRDetails {
     }
     /// Checks if the bit depth value is valid and that the provided color type is valid for the
     /// provided bit depth. See `color_type` and `bit_depth` fields for more details.
-    fn is_valid_bit_depth_for_color_type(color_type: u8, bit_depth: u8) -> std::io::Result<()> {
+    fn is_valid_bit_depth_for_color_type(color_type: u8, bit_depth: u8) -> Result<(), PngError> {
+        Self::is_valid_color_type(color_type)?;
+
         let table = COLOR_TYPE_LOOKUP_TABLE[color_type as usize];
         if !table.contains(&bit_depth) {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
+            return Err(PngError::InvalidIhdr(
                 format!("Invalid bit depth for color type.\n\
                 color type: {}\n\
                 bit_depth: {}\n\
                 valid values: {:?}",
                         color_type,
                         bit_depth,
-                        VALID_BIT_DEPTHS[color_type as usize],
+                        table,
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Checks if the provided value is a valid color type. Valid color types: 0, 2, 3, 4, 6
+    fn is_valid_color_type(color_type: u8) -> Result<(), PngError> {
+        if !VALID_COLOR_TYPES.contains(&color_type) {
+            return Err(PngError::InvalidIhdr(
+                format!("Invalid color type: {}\n\
+                Valid values: {:?}",
+                        color_type,
+                        VALID_COLOR_TYPES,
                 ),
             ));
         }
@@ -353,10 +473,9 @@ impl IHDRDetails {
         Ok(())
     }
     /// Checks if the provided value is a valid interlace method. Must be 1 or 0 in the current spec.
-    fn is_valid_interlace_method(interlace_method: u8) -> std::io::Result<()> {
+    fn is_valid_interlace_method(interlace_method: u8) -> Result<(), PngError> {
         if interlace_method != 1 && interlace_method != 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
+            return Err(PngError::InvalidIhdr(
                 format!("Invalid interlace method. Must be This value must always be  0 (no \
                 interlace) or 1 (Adam7 interlace), in the current spec. interlace method: {}",
                         interlace_method,
@@ -367,12 +486,67 @@ impl IHDRDetails {
     }
 }
 
+/// The compression method of the image data. Only method 0 is defined by the spec.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CompressionMethod {
+    /// Method 0. zlib datastream with deflate compression, with a sliding window of at most 32768 bytes.
+    Deflate,
+    /// Any other value, which decoders must report as an error.
+    Unknown(u8),
+}
+
+impl From<u8> for CompressionMethod {
+    fn from(compression_method: u8) -> Self {
+        match compression_method {
+            0 => CompressionMethod::Deflate,
+            compression_method => CompressionMethod::Unknown(compression_method),
+        }
+    }
+}
+impl From<CompressionMethod> for u8 {
+    fn from(compression_method: CompressionMethod) -> Self {
+        match compression_method {
+            CompressionMethod::Deflate => 0,
+            CompressionMethod::Unknown(compression_method) => compression_method,
+        }
+    }
+}
+
+/// The filter method of the image data. Only method 0 is defined by the spec.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FilterMethod {
+    /// Method 0. Adaptive filtering, where each scanline picks one of the five basic filter types.
+    AdaptiveFiltering,
+    /// Any other value, which decoders must report as an error.
+    Unknown(u8),
+}
+
+impl From<u8> for FilterMethod {
+    fn from(filter_method: u8) -> Self {
+        match filter_method {
+            0 => FilterMethod::AdaptiveFiltering,
+            filter_method => FilterMethod::Unknown(filter_method),
+        }
+    }
+}
+impl From<FilterMethod> for u8 {
+    fn from(filter_method: FilterMethod) -> Self {
+        match filter_method {
+            FilterMethod::AdaptiveFiltering => 0,
+            FilterMethod::Unknown(filter_method) => filter_method,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::chunk::ty::critical::ihdr::IHDR;
+    use crate::chunk::ty::critical::ihdr::{CompressionMethod, FilterMethod, IHDRDetails, IHDR};
+    #[cfg(feature = "std")]
     use crate::PNGReader;
+    use alloc::string::ToString;
 
     #[test]
+    #[cfg(feature = "std")]
     #[allow(arithmetic_overflow)]
     fn read_header() {
         let png_file = std::fs::read("ferris.png").expect("Could not read png file");
@@ -385,4 +559,142 @@ mod tests {
         assert_eq!(header.get_width(), 460);
         assert_eq!(header.get_height(), 307);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_unaligned_header() {
+        use crate::chunk::refs::ChunkRefs;
+
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let (offset, hdr_chunk) = png.chunks_with_offsets().next().unwrap();
+        let chunk_len = hdr_chunk.get_length() as usize + 12;
+
+        // Copy the chunk to both an even and an odd offset, so that one of the IHDR structs is misaligned
+        // for any type with an alignment greater than 1.
+        for padding in 0..2 {
+            let mut buffer = vec![0; padding];
+            buffer.extend_from_slice(&png_file[offset..offset + chunk_len]);
+            let refs = ChunkRefs::from_raw(&buffer[padding..]).unwrap();
+            let header = IHDR::from_chunk_refs(&refs).unwrap();
+
+            assert_eq!(header.get_width(), 460);
+            assert_eq!(header.get_height(), 307);
+        }
+    }
+
+    #[test]
+    fn pixel_layout() {
+        let details = IHDRDetails::new(8, 6, 0, 0, 0).unwrap();
+        assert_eq!(details.channel_count(), 4);
+        assert_eq!(details.bits_per_pixel(), 32);
+        assert_eq!(details.bytes_per_scanline(460), 1 + 460 * 4);
+
+        let details = IHDRDetails::new(16, 2, 0, 0, 0).unwrap();
+        assert_eq!(details.channel_count(), 3);
+        assert_eq!(details.bits_per_pixel(), 48);
+
+        let details = IHDRDetails::new(16, 4, 0, 0, 0).unwrap();
+        assert_eq!(details.channel_count(), 2);
+        assert_eq!(details.bits_per_pixel(), 32);
+
+        let details = IHDRDetails::new(1, 0, 0, 0, 0).unwrap();
+        assert_eq!(details.channel_count(), 1);
+        assert_eq!(details.bits_per_pixel(), 1);
+        assert_eq!(details.bytes_per_scanline(9), 1 + 2);
+
+        let details = IHDRDetails::new(4, 3, 0, 0, 0).unwrap();
+        assert_eq!(details.channel_count(), 1);
+        assert_eq!(details.bits_per_pixel(), 4);
+        assert_eq!(details.bytes_per_scanline(3), 1 + 2);
+        assert_eq!(details.bytes_per_scanline(0), 1);
+    }
+
+    #[test]
+    fn adam7_pass_dimensions() {
+        let interlaced = IHDRDetails::new(8, 6, 0, 0, 1).unwrap();
+        let header = IHDR::new(8, 8, interlaced).unwrap();
+        assert_eq!(
+            header.adam7_pass_dimensions(),
+            Some([(1, 1), (1, 1), (2, 1), (2, 2), (4, 2), (4, 4), (8, 4)])
+        );
+
+        let header = IHDR::new(3, 5, interlaced).unwrap();
+        assert_eq!(
+            header.adam7_pass_dimensions(),
+            Some([(1, 1), (0, 1), (1, 1), (1, 2), (2, 1), (1, 3), (3, 2)])
+        );
+
+        let not_interlaced = IHDRDetails::new(8, 6, 0, 0, 0).unwrap();
+        assert_eq!(IHDR::new(8, 8, not_interlaced).unwrap().adam7_pass_dimensions(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn resize_round_trip() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let mut header = png.get_validated_header().unwrap();
+        let hdr_chunk = png.get_chunk_of_type("IHDR").unwrap();
+        assert_eq!(header.to_bytes(), hdr_chunk.get_chunk_data());
+
+        assert!(header.set_width(920));
+        assert!(header.set_height(614));
+        let chunk = header.to_chunk().unwrap();
+        let refs = chunk.as_chunk_refs();
+        let resized = IHDR::from_chunk_refs(&refs).unwrap();
+        resized.validate().unwrap();
+
+        assert_eq!(resized.get_width(), 920);
+        assert_eq!(resized.get_height(), 614);
+        assert_eq!(resized.get_details().get_color_type(), 6);
+        assert_eq!(resized.to_bytes()[8..], hdr_chunk.get_chunk_data()[8..]);
+    }
+
+    #[test]
+    fn invalid_color_type() {
+        for color_type in [1, 5, 7, 255] {
+            assert!(IHDRDetails::new(8, color_type, 0, 0, 0).is_err());
+        }
+
+        let mut details = IHDRDetails::new(8, 6, 0, 0, 0).unwrap();
+        assert!(details.set_color_type(7).is_err());
+        assert!(details.set_bit_depth_and_color_type(7, 8).is_err());
+
+        let err = IHDRDetails::new(16, 3, 0, 0, 0).err().unwrap();
+        assert!(err.to_string().contains("valid values: [1, 2, 4, 8]"), "{err}");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn to_chunk() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let hdr_chunk = png.get_chunk_of_type("IHDR").unwrap();
+        let header = IHDR::from_chunk_refs(&hdr_chunk).unwrap();
+
+        let chunk = header.to_chunk().unwrap();
+        let refs = chunk.as_chunk_refs();
+        assert_eq!(refs.get_chunk_data(), hdr_chunk.get_chunk_data());
+        assert_eq!(refs.get_crc(), hdr_chunk.get_crc());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn compression_and_filter_methods() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let header = png.get_validated_header().unwrap();
+
+        assert_eq!(header.get_details().compression(), CompressionMethod::Deflate);
+        assert_eq!(header.get_details().filter(), FilterMethod::AdaptiveFiltering);
+
+        for value in [0, 1, 255] {
+            assert_eq!(u8::from(CompressionMethod::from(value)), value);
+            assert_eq!(u8::from(FilterMethod::from(value)), value);
+        }
+        assert_eq!(CompressionMethod::from(1), CompressionMethod::Unknown(1));
+        assert_eq!(FilterMethod::from(2), FilterMethod::Unknown(2));
+    }
 }
@@ -1 +1,2 @@
 pub mod ihdr;
+pub mod plte;
\ No newline at end of file
@@ -0,0 +1,126 @@
+use crate::error::PngError;
+use crate::chunk::refs::ChunkRefs;
+use alloc::format;
+
+/// The PLTE chunk contains from 1 to 256 palette entries, each a three-byte series of the form:
+///
+///    Red:   1 byte (0 = black, 255 = red)
+///    Green: 1 byte (0 = black, 255 = green)
+///    Blue:  1 byte (0 = black, 255 = blue)
+///
+/// The number of entries is determined from the chunk length. A chunk length not divisible by 3 is an
+/// error.
+#[repr(transparent)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct PLTE {
+    /// Palette entries, in RGB order.
+    entries: [[u8; 3]],
+}
+
+const PLTE_ENTRY_SIZE: usize = 3;
+const MAX_PLTE_ENTRIES: usize = 256;
+
+impl PLTE {
+    /// Checks that the palette has between 1 and 256 entries.
+    pub fn validate(&self) -> Result<(), PngError> {
+        if self.entries.is_empty() || self.entries.len() > MAX_PLTE_ENTRIES {
+            return Err(PngError::InvalidData(
+                format!("Invalid number of palette entries. Must be 1-{MAX_PLTE_ENTRIES}. entries: {}",
+                        self.entries.len(),
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Gets the palette entry at the provided index as an RGB triple.
+    #[inline(always)]
+    pub fn get_entry(&self, index: usize) -> Option<[u8; 3]> {
+        self.entries.get(index).copied()
+    }
+    /// Gets the number of palette entries.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Returns true if the palette has no entries. A palette returned from `from_chunk_refs` always has
+    /// at least one entry.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Iterates over the palette entries as (red, green, blue) triples.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, u8, u8)> + '_ {
+        self.entries.iter().map(|&[r, g, b]| (r, g, b))
+    }
+}
+
+// Associated functions
+impl PLTE {
+    /// Provides a reference to a PLTE provided the chunk_type matches "PLTE" and the size of the data
+    /// is a non-zero multiple of 3.
+    pub fn from_chunk_refs<'a>(chunk_refs: &'a ChunkRefs<'a>) -> Option<&'a PLTE> {
+        if chunk_refs.get_chunk_type() != "PLTE" {
+            return None;
+        }
+        let data = chunk_refs.get_chunk_data();
+        if data.is_empty() || !data.len().is_multiple_of(PLTE_ENTRY_SIZE) {
+            return None;
+        }
+
+        let entries = unsafe {
+            core::slice::from_raw_parts(data.as_ptr() as *const [u8; 3], data.len() / PLTE_ENTRY_SIZE)
+        };
+        Some(unsafe { &*(entries as *const [[u8; 3]] as *const PLTE) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::builder::PNGBuilder;
+    use crate::chunk::PNGChunk;
+    use crate::chunk::ty::critical::plte::PLTE;
+    #[cfg(feature = "std")]
+    use crate::PNGReader;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_palette() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        // ferris.png is truecolor with alpha, so it has no palette.
+        assert!(png.get_chunk_of_type("PLTE").is_none());
+
+        let palette_png_file = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 3, 0, 0, 0]).unwrap())
+            .with_chunk(PNGChunk::new("PLTE", &[255, 0, 0, 0, 255, 0, 0, 0, 255]).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let palette_png = PNGReader::new(&palette_png_file[..]).expect("Could not validate PNG.");
+
+        let plte_chunk = palette_png.get_chunk_of_type("PLTE").unwrap();
+        let palette = PLTE::from_chunk_refs(&plte_chunk).unwrap();
+        palette.validate().unwrap();
+
+        assert_eq!(palette.len(), 3);
+        assert_eq!(palette.get_entry(0), Some([255, 0, 0]));
+        assert_eq!(palette.get_entry(3), None);
+        assert_eq!(palette.iter().last(), Some((0, 0, 255)));
+    }
+
+    #[test]
+    fn invalid_palette() {
+        let chunk = PNGChunk::new("PLTE", &[255, 0, 0, 0]).unwrap();
+        assert!(PLTE::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+
+        let chunk = PNGChunk::new("PLTE", &[]).unwrap();
+        assert!(PLTE::from_chunk_refs(&chunk.as_chunk_refs()).is_none());
+
+        let chunk = PNGChunk::new("PLTE", &[0; 257 * 3]).unwrap();
+        let refs = chunk.as_chunk_refs();
+        let palette = PLTE::from_chunk_refs(&refs).unwrap();
+        assert!(palette.validate().is_err());
+    }
+}
@@ -1,10 +1,28 @@
-#![allow(unused)]
 mod consts;
+pub mod ancillary;
 pub mod critical;
 
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::ty::ancillary::actl::ACTL;
+use crate::chunk::ty::ancillary::chrm::CHRM;
+use crate::chunk::ty::ancillary::exif::EXIF;
+use crate::chunk::ty::ancillary::fctl::FCTL;
+use crate::chunk::ty::ancillary::gama::GAMA;
+use crate::chunk::ty::ancillary::itxt::ITXt;
+use crate::chunk::ty::ancillary::offs::OFFS;
+use crate::chunk::ty::ancillary::phys::PHYS;
+use crate::chunk::ty::ancillary::splt::SPLT;
+use crate::chunk::ty::ancillary::srgb::SRGB;
+use crate::chunk::ty::ancillary::text::{decode_latin1, TEXt};
+use crate::chunk::ty::ancillary::time::TIME;
+use crate::chunk::ty::ancillary::ztxt::ZTXt;
 use crate::chunk::ty::consts::BIT_FIVE_MASK;
-use std::io::{Error, ErrorKind};
+use crate::chunk::ty::critical::ihdr::IHDR;
+use crate::chunk::ty::critical::plte::PLTE;
+use crate::error::PngError;
 use bytemuck::AnyBitPattern;
+use alloc::format;
+use alloc::string::ToString;
 
 /// A 4-byte chunk type code. For convenience in description and in examining PNG files, type codes
 /// are restricted to consist of uppercase and lowercase ASCII letters (A-Z and a-z, or 65-90 and 97-122
@@ -37,7 +55,7 @@ pub struct ChunkType {
     _type: [u8; 4],
 }
 
-const _: () = assert!(std::mem::size_of::<ChunkType>() == std::mem::size_of::<u32>());
+const _: () = assert!(core::mem::size_of::<ChunkType>() == core::mem::size_of::<u32>());
 
 impl ChunkType {
     /// A 4-byte chunk type code. For convenience in description and in examining PNG files, type codes
@@ -52,20 +70,22 @@ impl ChunkType {
     /// decimal).
     #[inline(always)]
     pub fn as_str(&self) -> &str {
-        unsafe { std::str::from_utf8_unchecked(&self._type) }
+        unsafe { core::str::from_utf8_unchecked(&self._type) }
     }
     /// A 4-byte chunk type code. For convenience in description and in examining PNG files, type codes
     /// are restricted to consist of uppercase and lowercase ASCII letters (A-Z and a-z, or 65-90 and 97-122
     /// decimal).
     #[inline(always)]
-    pub fn set_chunk_type(&mut self, chunk_type: &str) -> std::io::Result<()> {
+    pub fn set_chunk_type(&mut self, chunk_type: &str) -> Result<(), PngError> {
         Self::validate_chunk_type(chunk_type)?;
 
         self._type.copy_from_slice(chunk_type.as_bytes());
         Ok(())
     }
-    pub fn validate(&self) -> std::io::Result<()> {
-        Self::validate_chunk_type(self.as_str())
+    /// Checks that the chunk type is 4 ASCII letters. The bytes are checked directly, so this is safe to
+    /// call on a chunk type read from an untrusted file, before `as_str` is used.
+    pub fn validate(&self) -> Result<(), PngError> {
+        Self::validate_chunk_type_bytes(&self._type)
     }
     /// Chunks that are not strictly necessary in order to meaningfully display the contents of the file
     /// are known as "ancillary" chunks. A decoder encountering an unknown chunk in which the ancillary
@@ -122,32 +142,204 @@ impl ChunkType {
     pub fn is_safe_to_copy(&self) -> bool {
         self._type[3] & BIT_FIVE_MASK != 0
     }
+    /// Classifies the chunk type as one of the registered public chunk types, or `KnownChunk::Unknown`.
+    /// Chunk types are compared byte for byte, so the case of each letter matters.
+    pub fn known_kind(&self) -> KnownChunk {
+        match &self._type {
+            b"IHDR" => KnownChunk::Ihdr,
+            b"PLTE" => KnownChunk::Plte,
+            b"IDAT" => KnownChunk::Idat,
+            b"IEND" => KnownChunk::Iend,
+            b"tRNS" => KnownChunk::Trns,
+            b"cHRM" => KnownChunk::Chrm,
+            b"gAMA" => KnownChunk::Gama,
+            b"iCCP" => KnownChunk::Iccp,
+            b"sBIT" => KnownChunk::Sbit,
+            b"sRGB" => KnownChunk::Srgb,
+            b"tEXt" => KnownChunk::Text,
+            b"zTXt" => KnownChunk::Ztxt,
+            b"iTXt" => KnownChunk::Itxt,
+            b"bKGD" => KnownChunk::Bkgd,
+            b"hIST" => KnownChunk::Hist,
+            b"pHYs" => KnownChunk::Phys,
+            b"sPLT" => KnownChunk::Splt,
+            b"tIME" => KnownChunk::Time,
+            b"eXIf" => KnownChunk::Exif,
+            b"acTL" => KnownChunk::Actl,
+            b"fcTL" => KnownChunk::Fctl,
+            b"fdAT" => KnownChunk::Fdat,
+            _ => KnownChunk::Unknown,
+        }
+    }
 }
 // Associated functions
 impl ChunkType {
-    pub fn new(chunk_type_str: &str) -> std::io::Result<Self> {
+    pub fn new(chunk_type_str: &str) -> Result<Self, PngError> {
         let mut chunk = ChunkType { _type: [0; 4] };
         chunk.set_chunk_type(chunk_type_str)?;
 
         Ok(chunk)
     }
-    pub fn validate_chunk_type(chunk_type: &str) -> std::io::Result<()> {
+    pub fn validate_chunk_type(chunk_type: &str) -> Result<(), PngError> {
+        Self::validate_chunk_type_bytes(chunk_type.as_bytes())
+    }
+    fn validate_chunk_type_bytes(chunk_type: &[u8]) -> Result<(), PngError> {
         if chunk_type.len() != 4 {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                "Chunk type is not 4 bytes long. Invalid Chunk type.",
-            ));
+            return Err(PngError::InvalidChunkType {
+                offset: None,
+                reason: "Chunk type is not 4 bytes long.".to_string(),
+            });
         }
 
-        for chr in chunk_type.as_bytes() {
+        for chr in chunk_type {
             if !chr.is_ascii_alphabetic() {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    format!("Chunk type contains invalid character. {}", chr),
-                ));
+                return Err(PngError::InvalidChunkType {
+                    offset: None,
+                    reason: format!(
+                        "Chunk type contains invalid byte 0x{chr:02X}. Chunk type bytes: {:02X?}",
+                        chunk_type
+                    ),
+                });
             }
         }
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// The registered public chunk types, including the APNG chunk types. Anything else, including private
+/// chunks, is `Unknown`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KnownChunk {
+    Ihdr,
+    Plte,
+    Idat,
+    Iend,
+    Trns,
+    Chrm,
+    Gama,
+    Iccp,
+    Sbit,
+    Srgb,
+    Text,
+    Ztxt,
+    Itxt,
+    Bkgd,
+    Hist,
+    Phys,
+    Splt,
+    Time,
+    Exif,
+    Actl,
+    Fctl,
+    Fdat,
+    Unknown,
+}
+
+impl KnownChunk {
+    /// Returns true for the four critical chunk types: IHDR, PLTE, IDAT and IEND.
+    pub fn is_critical(&self) -> bool {
+        matches!(self, KnownChunk::Ihdr | KnownChunk::Plte | KnownChunk::Idat | KnownChunk::Iend)
+    }
+}
+
+/// Dispatches the provided chunk to the typed view for its chunk type and runs that type's `validate()`
+/// method. Chunk types that this crate does not have a typed view for are passed through without any
+/// checks. sBIT and bKGD are also passed through, because their layout depends on the color type and
+/// bit depth in IHDR, which this function does not have.
+pub fn validate_known_chunk(chunk_refs: &ChunkRefs<'_>) -> Result<(), PngError> {
+    match chunk_refs.get_chunk_type() {
+        "IHDR" => Ok(IHDR::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate()?),
+        "PLTE" => PLTE::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "acTL" => ACTL::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "cHRM" => CHRM::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "eXIf" => EXIF::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "fcTL" => FCTL::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "gAMA" => GAMA::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "oFFs" => OFFS::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "pHYs" => PHYS::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "sPLT" => SPLT::from_chunk_refs(chunk_refs)
+            .map(|_| ())
+            .ok_or_else(|| PngError::InvalidData("sPLT chunk has an invalid palette name, sample depth or entry size".to_string())),
+        "sRGB" => SRGB::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "tIME" => TIME::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| invalid_known_chunk_size(chunk_refs))?
+            .validate(),
+        "tEXt" => {
+            let text = TEXt::from_chunk_refs(chunk_refs)
+                .ok_or_else(|| PngError::InvalidData("tEXt chunk is missing its keyword or null separator".to_string()))?;
+            TEXt::validate_keyword(&decode_latin1(text.get_keyword_bytes()))
+        }
+        "zTXt" => ZTXt::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| PngError::InvalidData("zTXt chunk is missing its keyword or compression method".to_string()))?
+            .validate(),
+        "iTXt" => ITXt::from_chunk_refs(chunk_refs)
+            .ok_or_else(|| PngError::InvalidData("iTXt chunk is missing its keyword, compression bytes or null separators".to_string()))?
+            .validate(),
+        _ => Ok(()),
+    }
+}
+fn invalid_known_chunk_size(chunk_refs: &ChunkRefs<'_>) -> PngError {
+    PngError::InvalidData(
+        format!(
+            "Chunk data is not a valid size for chunk type. Chunk type: {} Chunk length: {}",
+            chunk_refs.get_chunk_type(),
+            chunk_refs.get_length(),
+        ),
+    )
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::chunk::ty::{ChunkType, KnownChunk};
+    use crate::PNGReader;
+
+    #[test]
+    fn known_kind() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let kinds: Vec<KnownChunk> = png
+            .into_iter()
+            .map(|chunk| ChunkType::new(chunk.get_chunk_type()).unwrap().known_kind())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                KnownChunk::Ihdr,
+                KnownChunk::Srgb,
+                KnownChunk::Exif,
+                KnownChunk::Phys,
+                KnownChunk::Itxt,
+                KnownChunk::Idat,
+                KnownChunk::Idat,
+                KnownChunk::Idat,
+                KnownChunk::Iend,
+            ]
+        );
+        assert_eq!(kinds.iter().filter(|kind| kind.is_critical()).count(), 5);
+
+        assert_eq!(ChunkType::new("prVt").unwrap().known_kind(), KnownChunk::Unknown);
+        assert_eq!(ChunkType::new("ihdr").unwrap().known_kind(), KnownChunk::Unknown);
+    }
+}
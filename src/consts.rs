@@ -3,5 +3,13 @@ use crate::chunk::header::ChunkHeader;
 
 pub const PNG_SIGNATURE_LENGTH: usize = 0x8;
 pub const PNG_SIGNATURE: [u8; PNG_SIGNATURE_LENGTH] = [0x89, 0x50, 0x4E, 0x47, 0xD, 0xA, 0x1A, 0xA];
-pub const CHUNK_HEADER_SIZE: usize = std::mem::size_of::<ChunkHeader>();
-pub const CHUNK_CRC_SIZE: usize = std::mem::size_of::<ChunkCRC>();
+pub const CHUNK_HEADER_SIZE: usize = core::mem::size_of::<ChunkHeader>();
+pub const CHUNK_CRC_SIZE: usize = core::mem::size_of::<ChunkCRC>();
+/// The largest chunk data length the spec allows, 2^31 - 1.
+pub const MAX_CHUNK_LENGTH: u32 = 0x7FFFFFFF;
+/// The most bytes that the text of a zTXt or iTXt chunk is decompressed to, 8 MiB. Text that is longer
+/// than this fails to decompress, so that a small chunk can not expand into gigabytes of text.
+pub const MAX_DECOMPRESSED_TEXT_LENGTH: usize = 0x800000;
+/// Chunk types that `PNGReader::validate_allowed_types` always allows, since every PNG needs them. PLTE
+/// is not included, and must be listed explicitly.
+pub const ALWAYS_ALLOWED_CHUNK_TYPES: [&str; 3] = ["IHDR", "IDAT", "IEND"];
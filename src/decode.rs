@@ -0,0 +1,430 @@
+use crate::chunk::ty::critical::ihdr::{IHDRDetails, IHDR};
+use crate::chunk::ty::critical::plte::PLTE;
+use crate::error::PngError;
+use crate::iter::IdatReader;
+use crate::PNGReader;
+use flate2::read::ZlibDecoder;
+use std::io::Read;
+
+/// The highest filter type defined by filter method 0 (Paeth).
+const MAX_FILTER_TYPE: u8 = 4;
+
+impl PNGReader<'_> {
+    /// Decodes the top-left pixel of the image and returns it as 16-bit RGBA. Only the start of the
+    /// first scanline is decompressed, so this is much cheaper than decoding the whole image.
+    ///
+    /// Samples are scaled up to 16 bits, palette images are looked up in the PLTE chunk, and the alpha
+    /// channel comes from the tRNS chunk for image types that do not have their own alpha channel.
+    ///
+    /// For Adam7 interlaced images the first pixel of pass 1 is returned, which is also the top-left
+    /// pixel of the image.
+    pub fn first_pixel(&self) -> Result<[u16; 4], PngError> {
+        let header = self.get_validated_header()?;
+        let details = header.get_details();
+
+        // The first byte of every scanline is the filter type. For the first pixel of the first
+        // scanline, the pixels to the left and above are treated as 0, so every filter type leaves the
+        // filtered bytes unchanged, and no unfiltering has to be done.
+        let mut scanline_start = vec![0; 1 + bytes_per_pixel(details)];
+        ZlibDecoder::new(IdatReader::new(self)).read_exact(&mut scanline_start)?;
+
+        let filter_type = scanline_start[0];
+        if filter_type > MAX_FILTER_TYPE {
+            return Err(PngError::InvalidData(
+                format!("Invalid filter type. Must be 0-{MAX_FILTER_TYPE}. filter type: {filter_type}"),
+            ));
+        }
+
+        self.pixel_to_rgba(details, &scanline_start[1..])
+    }
+    /// Returns true if every pixel in the image has the same value for its red, green and blue samples,
+    /// meaning the image could be stored as grayscale without losing any information. The alpha channel
+    /// is ignored.
+    ///
+    /// Truecolor images are fully decoded, stopping at the first pixel that is not gray. Grayscale images
+    /// are always gray. Palette images are checked by their palette entries instead of their pixels, so
+    /// an unused colored palette entry will make this return false.
+    pub fn is_effectively_grayscale(&self) -> Result<bool, PngError> {
+        let header = self.get_validated_header()?;
+        let details = header.get_details();
+        let sample_size = details.get_bit_depth() as usize / 8;
+        let pixel_size = details.channel_count() as usize * sample_size;
+
+        match details.get_color_type() {
+            0 | 4 => Ok(true),
+            3 => {
+                let plte_chunk = self.get_chunk_of_type("PLTE").ok_or_else(|| {
+                    PngError::InvalidData("Palette image does not contain a PLTE chunk".to_string())
+                })?;
+                let palette = PLTE::from_chunk_refs(&plte_chunk)
+                    .ok_or_else(|| PngError::InvalidData("PLTE chunk is not a valid size".to_string()))?;
+
+                let is_gray = palette.iter().all(|(r, g, b)| r == g && g == b);
+
+                Ok(is_gray)
+            }
+            _ => {
+                let mut is_gray = true;
+                self.for_each_scanline(&header, |scanline| {
+                    is_gray = scanline.chunks_exact(pixel_size).all(|pixel| {
+                        let (r, g, b) = (
+                            &pixel[..sample_size],
+                            &pixel[sample_size..sample_size * 2],
+                            &pixel[sample_size * 2..sample_size * 3],
+                        );
+                        r == g && g == b
+                    });
+                    is_gray
+                })?;
+
+                Ok(is_gray)
+            }
+        }
+    }
+    /// Decompresses all of the image data and checks that its length matches the length the IHDR chunk
+    /// describes. For Adam7 interlaced images, that is the sum of the filtered scanlines of all seven
+    /// passes, skipping empty passes. For non-interlaced images, it is the filtered scanlines of the
+    /// whole image. Fails with the expected and actual lengths if they do not match, which usually means
+    /// the interlace method in the IHDR chunk does not agree with how the data was written.
+    pub fn validate_interlace_consistency(&self) -> Result<(), PngError> {
+        let header = self.get_validated_header()?;
+        let expected = expected_data_len(&header);
+        let actual = std::io::copy(&mut ZlibDecoder::new(IdatReader::new(self)), &mut std::io::sink())?;
+
+        if actual != expected {
+            return Err(PngError::InvalidData(
+                format!("Decompressed image data length does not match the IHDR chunk. Interlace method: {} Expected: {expected} Actual: {actual}",
+                        header.get_details().get_interlace_method(),
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Decompresses and unfilters the image data one scanline at a time, calling `f` with each unfiltered
+    /// scanline, without the filter type byte. If `f` returns false, decoding stops early.
+    ///
+    /// Interlaced images are decoded one Adam7 pass at a time, so the scanlines are passed to `f` in the
+    /// order they are stored, not in the order they appear in the image.
+    fn for_each_scanline(&self, header: &IHDR, mut f: impl FnMut(&[u8]) -> bool) -> Result<(), PngError> {
+        let details = header.get_details();
+        let bytes_per_pixel = bytes_per_pixel(details);
+        let mut decoder = ZlibDecoder::new(IdatReader::new(self));
+
+        for (width, height) in pass_dimensions(header) {
+            if width == 0 || height == 0 {
+                continue;
+            }
+
+            let scanline_len = scanline_len(details, width);
+            let mut previous = vec![0; scanline_len];
+            let mut current = vec![0; scanline_len];
+
+            for _ in 0..height {
+                let mut filter_type = [0];
+                decoder.read_exact(&mut filter_type)?;
+                decoder.read_exact(&mut current)?;
+                unfilter(filter_type[0], &mut current, &previous, bytes_per_pixel)?;
+
+                if !f(&current) {
+                    return Ok(());
+                }
+
+                std::mem::swap(&mut previous, &mut current);
+            }
+        }
+
+        Ok(())
+    }
+    /// Converts the bytes of a single unfiltered pixel to 16-bit RGBA.
+    fn pixel_to_rgba(&self, details: &IHDRDetails, pixel: &[u8]) -> Result<[u16; 4], PngError> {
+        let bit_depth = details.get_bit_depth();
+        let sample = |i: usize| get_sample(pixel, i, bit_depth);
+        let scale = |value: u16| scale_sample(value, bit_depth);
+        let transparency = self.get_chunk_of_type("tRNS");
+        let transparency = transparency.as_ref().map(|chunk| chunk.get_chunk_data());
+
+        let rgba = match details.get_color_type() {
+            0 => {
+                let gray = sample(0);
+                let alpha = match transparency {
+                    Some(trns) if trns.len() >= 2 && u16::from_be_bytes([trns[0], trns[1]]) == gray => 0,
+                    _ => u16::MAX,
+                };
+                [scale(gray), scale(gray), scale(gray), alpha]
+            }
+            2 => {
+                let rgb = [sample(0), sample(1), sample(2)];
+                let alpha = match transparency {
+                    Some(trns) if trns.len() >= 6 && rgb
+                        .iter()
+                        .zip(trns.chunks_exact(2))
+                        .all(|(&value, trns_value)| u16::from_be_bytes([trns_value[0], trns_value[1]]) == value) => 0,
+                    _ => u16::MAX,
+                };
+                [scale(rgb[0]), scale(rgb[1]), scale(rgb[2]), alpha]
+            }
+            3 => {
+                let index = sample(0) as usize;
+                let plte_chunk = self.get_chunk_of_type("PLTE").ok_or_else(|| {
+                    PngError::InvalidData("Palette image does not contain a PLTE chunk".to_string())
+                })?;
+                let [r, g, b] = PLTE::from_chunk_refs(&plte_chunk)
+                    .and_then(|palette| palette.get_entry(index))
+                    .ok_or_else(|| PngError::InvalidData(
+                        format!("Palette index is out of range of the PLTE chunk. index: {index}"),
+                    ))?;
+                let alpha = transparency
+                    .and_then(|trns| trns.get(index).copied())
+                    .unwrap_or(u8::MAX);
+                [r, g, b, alpha].map(|value| scale_sample(value as u16, 8))
+            }
+            4 => {
+                let gray = scale(sample(0));
+                [gray, gray, gray, scale(sample(1))]
+            }
+            6 => [scale(sample(0)), scale(sample(1)), scale(sample(2)), scale(sample(3))],
+            color_type => {
+                return Err(PngError::InvalidData(
+                    format!("Invalid color type: {color_type}"),
+                ))
+            }
+        };
+
+        Ok(rgba)
+    }
+}
+
+/// Returns the width and height of each pass of the image. Non-interlaced images have a single pass
+/// with the dimensions of the whole image. Adam7 passes can be empty for small images.
+fn pass_dimensions(header: &IHDR) -> Vec<(u32, u32)> {
+    match header.adam7_pass_dimensions() {
+        Some(passes) => passes.to_vec(),
+        None => vec![(header.get_width() as u32, header.get_height() as u32)],
+    }
+}
+/// Returns the length of the decompressed image data, which is every filtered scanline of every pass,
+/// including the filter type byte at the start of each scanline. Empty passes have no scanlines.
+fn expected_data_len(header: &IHDR) -> u64 {
+    pass_dimensions(header)
+        .into_iter()
+        .filter(|&(width, height)| width != 0 && height != 0)
+        .map(|(width, height)| height as u64 * header.get_details().bytes_per_scanline(width))
+        .sum()
+}
+/// Length of a single unfiltered scanline of the provided width, without the filter type byte.
+fn scanline_len(details: &IHDRDetails, width: u32) -> usize {
+    details.bytes_per_scanline(width) as usize - 1
+}
+/// Reverses the filter of a single scanline in place. `previous` is the unfiltered previous scanline of
+/// the same pass, or all zeros for the first scanline.
+fn unfilter(filter_type: u8, current: &mut [u8], previous: &[u8], bytes_per_pixel: usize) -> Result<(), PngError> {
+    match filter_type {
+        0 => {}
+        1 => {
+            for i in bytes_per_pixel..current.len() {
+                current[i] = current[i].wrapping_add(current[i - bytes_per_pixel]);
+            }
+        }
+        2 => {
+            for (value, above) in current.iter_mut().zip(previous) {
+                *value = value.wrapping_add(*above);
+            }
+        }
+        3 => {
+            for i in 0..current.len() {
+                let left = if i >= bytes_per_pixel { current[i - bytes_per_pixel] } else { 0 };
+                let average = ((left as u16 + previous[i] as u16) / 2) as u8;
+                current[i] = current[i].wrapping_add(average);
+            }
+        }
+        4 => {
+            for i in 0..current.len() {
+                let (left, upper_left) = if i >= bytes_per_pixel {
+                    (current[i - bytes_per_pixel], previous[i - bytes_per_pixel])
+                } else {
+                    (0, 0)
+                };
+                current[i] = current[i].wrapping_add(paeth_predictor(left, previous[i], upper_left));
+            }
+        }
+        _ => {
+            return Err(PngError::InvalidData(
+                format!("Invalid filter type. Must be 0-{MAX_FILTER_TYPE}. filter type: {filter_type}"),
+            ))
+        }
+    }
+
+    Ok(())
+}
+/// Picks whichever of left, above and upper left is closest to `left + above - upper_left`.
+fn paeth_predictor(left: u8, above: u8, upper_left: u8) -> u8 {
+    let estimate = left as i16 + above as i16 - upper_left as i16;
+    let left_distance = (estimate - left as i16).abs();
+    let above_distance = (estimate - above as i16).abs();
+    let upper_left_distance = (estimate - upper_left as i16).abs();
+
+    if left_distance <= above_distance && left_distance <= upper_left_distance {
+        left
+    } else if above_distance <= upper_left_distance {
+        above
+    } else {
+        upper_left
+    }
+}
+/// Number of bytes needed to hold a single pixel, rounded up to 1 for bit depths lower than 8.
+fn bytes_per_pixel(details: &IHDRDetails) -> usize {
+    (details.bits_per_pixel() as usize).div_ceil(8)
+}
+/// Gets the sample at the provided index in the pixel. Bit depths lower than 8 are only allowed for
+/// single channel color types, so only the first sample in the first byte is read for those.
+fn get_sample(pixel: &[u8], index: usize, bit_depth: u8) -> u16 {
+    match bit_depth {
+        16 => u16::from_be_bytes([pixel[index * 2], pixel[index * 2 + 1]]),
+        8 => pixel[index] as u16,
+        _ => (pixel[0] >> (8 - bit_depth)) as u16,
+    }
+}
+/// Scales a sample of the provided bit depth up to the full 16-bit range.
+fn scale_sample(value: u16, bit_depth: u8) -> u16 {
+    let max = (1u32 << bit_depth) - 1;
+    (value as u32 * u16::MAX as u32 / max) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::PNGBuilder;
+    use crate::chunk::PNGChunk;
+    use crate::PNGReader;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn first_pixel() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        // The corner of ferris.png is fully transparent.
+        assert_eq!(png.first_pixel().unwrap(), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn first_pixel_palette() {
+        let png_file = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 2, 0, 0, 0, 1, 8, 3, 0, 0, 0]).unwrap())
+            .with_chunk(PNGChunk::new("PLTE", &[255, 0, 0, 0, 255, 0]).unwrap())
+            .with_chunk(PNGChunk::new("tRNS", &[255, 128]).unwrap())
+            .with_chunk(PNGChunk::new("IDAT", &compress(&[0, 1, 0])).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        assert_eq!(png.first_pixel().unwrap(), [0, u16::MAX, 0, 128 * 257]);
+    }
+
+    #[test]
+    fn first_pixel_low_bit_depth() {
+        // 2-bit grayscale, split across two IDAT chunks, using the Sub filter.
+        let image_data = compress(&[1, 0b1001_0000]);
+        let png_file = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 4, 0, 0, 0, 1, 2, 0, 0, 0, 0]).unwrap())
+            .with_chunk(PNGChunk::new("IDAT", &image_data[..3]).unwrap())
+            .with_chunk(PNGChunk::new("IDAT", &image_data[3..]).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let gray = u16::MAX / 3 * 2;
+        assert_eq!(png.first_pixel().unwrap(), [gray, gray, gray, u16::MAX]);
+    }
+
+    #[test]
+    fn effectively_grayscale() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(!png.is_effectively_grayscale().unwrap());
+
+        // 2x2 8-bit RGB. The first row uses the Sub filter and the second row uses the Paeth filter, so
+        // the filtered bytes are not gray, even though every unfiltered pixel is.
+        let gray_data = compress(&[1, 10, 10, 10, 5, 5, 5, 4, 0, 0, 0, 240, 240, 240]);
+        let png_file = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0]).unwrap())
+            .with_chunk(PNGChunk::new("IDAT", &gray_data).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(png.is_effectively_grayscale().unwrap());
+
+        let color_data = compress(&[0, 10, 10, 10, 15, 15, 15, 2, 0, 0, 0, 0, 1, 0]);
+        let png_file = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0]).unwrap())
+            .with_chunk(PNGChunk::new("IDAT", &color_data).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(!png.is_effectively_grayscale().unwrap());
+    }
+
+    #[test]
+    fn effectively_grayscale_interlaced() {
+        // 2x2 8-bit RGBA, Adam7 interlaced. Pass 1 holds (0, 0), pass 6 holds (1, 0) and pass 7 holds
+        // the second row. The other passes are empty.
+        let image_data = compress(&[
+            0, 1, 1, 1, 255,
+            0, 2, 2, 2, 255,
+            1, 3, 3, 3, 0, 1, 1, 1, 0,
+        ]);
+        let png_file = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 2, 0, 0, 0, 2, 8, 6, 0, 0, 1]).unwrap())
+            .with_chunk(PNGChunk::new("IDAT", &image_data).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        assert!(png.is_effectively_grayscale().unwrap());
+    }
+
+    #[test]
+    fn interlace_consistency() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        png.validate_interlace_consistency().unwrap();
+
+        // 2x2 8-bit RGBA, Adam7 interlaced. Passes 1, 6 and 7 hold 1, 1 and 2 pixels.
+        let interlaced_data = compress(&[
+            0, 1, 1, 1, 255,
+            0, 2, 2, 2, 255,
+            0, 3, 3, 3, 0, 1, 1, 1, 0,
+        ]);
+        // The same pixels, but not interlaced.
+        let progressive_data = compress(&[
+            0, 1, 1, 1, 255, 2, 2, 2, 255,
+            0, 3, 3, 3, 0, 1, 1, 1, 0,
+        ]);
+        let build = |interlace_method: u8, image_data: &[u8]| {
+            PNGBuilder::new()
+                .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 2, 0, 0, 0, 2, 8, 6, 0, 0, interlace_method]).unwrap())
+                .with_chunk(PNGChunk::new("IDAT", image_data).unwrap())
+                .build()
+                .expect("Could not build PNG file")
+        };
+
+        for (interlace_method, image_data, is_consistent) in [
+            (1, &interlaced_data, true),
+            (0, &progressive_data, true),
+            (0, &interlaced_data, false),
+            (1, &progressive_data, false),
+        ] {
+            let png_file = build(interlace_method, image_data);
+            let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+            assert_eq!(png.validate_interlace_consistency().is_ok(), is_consistent);
+        }
+    }
+}
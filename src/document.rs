@@ -0,0 +1,81 @@
+use crate::PNGReader;
+use crate::error::PngError;
+use std::io::Read;
+
+/// A validated PNG file that owns its buffer. Unlike `PNGReader`, it does not borrow from anything, so it
+/// can be created straight from a `Read` source, and stored in structs without a lifetime.
+pub struct PNGDocument {
+    buffer: Vec<u8>,
+}
+
+impl PNGDocument {
+    /// Gets a `PNGReader` over the document's buffer. The buffer was validated when the document was
+    /// created, so it is not validated again.
+    pub fn reader(&self) -> PNGReader<'_> {
+        // Safety: The buffer is validated with `PNGReader::new` before the document is created, and it
+        // cannot be changed afterwards.
+        unsafe { PNGReader::new_unchecked(&self.buffer) }
+    }
+    /// Same as `reader`.
+    #[inline(always)]
+    pub fn as_reader(&self) -> PNGReader<'_> {
+        self.reader()
+    }
+    /// Gets the bytes of the whole PNG file.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+// Associated functions
+impl PNGDocument {
+    /// Takes ownership of `buffer` and validates it like `PNGReader::new`, including the crc of every
+    /// chunk.
+    pub fn new(buffer: Vec<u8>) -> Result<Self, PngError> {
+        PNGReader::new(&buffer)?;
+
+        Ok(PNGDocument { buffer })
+    }
+    /// Reads `reader` to the end and validates the result like `PNGDocument::new`.
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, PngError> {
+        let mut buffer = vec![];
+        reader.read_to_end(&mut buffer)?;
+
+        Self::new(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::document::PNGDocument;
+    use std::io::Cursor;
+
+    #[test]
+    fn new() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+
+        let document = PNGDocument::new(png_file.clone()).expect("Could not validate PNG.");
+        assert_eq!(document.as_bytes(), png_file);
+
+        let chunk_types: Vec<&str> = document.reader().into_iter().map(|c| c.get_chunk_type()).collect();
+        assert_eq!(chunk_types, ["IHDR", "sRGB", "eXIf", "pHYs", "iTXt", "IDAT", "IDAT", "IDAT", "IEND"]);
+
+        assert!(PNGDocument::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn from_reader() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+
+        let document = PNGDocument::from_reader(Cursor::new(&png_file)).expect("Could not validate PNG.");
+        let png = document.as_reader();
+        assert_eq!(png.get_validated_header().unwrap().get_width(), 460);
+        assert_eq!(png.get_all_chunk_info().len(), 9);
+
+        let mut corrupted = png_file.clone();
+        corrupted[20] ^= 0xFF;
+        assert!(PNGDocument::from_reader(Cursor::new(corrupted)).is_err());
+        assert!(PNGDocument::from_reader(Cursor::new(b"not a png")).is_err());
+    }
+}
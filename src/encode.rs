@@ -0,0 +1,246 @@
+use crate::builder::PNGBuilder;
+use crate::chunk::ty::ancillary::actl::ACTL;
+use crate::chunk::ty::ancillary::fctl::{FCTL, MAX_BLEND_OP, MAX_DISPOSE_OP};
+use crate::chunk::PNGChunk;
+use crate::error::PngError;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+/// Number of bytes in each RGBA pixel of a `FrameSpec`.
+const RGBA_PIXEL_SIZE: usize = 4;
+
+/// A single frame of an animated PNG. The pixels are 8-bit RGBA, row by row, with no filter bytes, and
+/// cover the region of the canvas described by the offsets and dimensions of the frame.
+pub struct FrameSpec {
+    /// Width of the frame region, in pixels.
+    pub width: u32,
+    /// Height of the frame region, in pixels.
+    pub height: u32,
+    /// X position of the frame region on the canvas.
+    pub x_offset: u32,
+    /// Y position of the frame region on the canvas.
+    pub y_offset: u32,
+    /// 8-bit RGBA pixels. Must be exactly `width * height * 4` bytes long.
+    pub pixels: Vec<u8>,
+    /// Numerator of the frame delay, in seconds.
+    pub delay_num: u16,
+    /// Denominator of the frame delay, in seconds. 0 is treated as 100 by decoders.
+    pub delay_den: u16,
+    /// What happens to the frame region before the next frame is rendered. 0 (none), 1 (background)
+    /// or 2 (previous).
+    pub dispose_op: u8,
+    /// How the frame is blended onto the canvas. 0 (source) or 1 (over).
+    pub blend_op: u8,
+}
+
+impl FrameSpec {
+    /// Creates a frame that covers the whole canvas, that does no disposal and overwrites the canvas.
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>, delay_num: u16, delay_den: u16) -> Self {
+        FrameSpec {
+            width,
+            height,
+            x_offset: 0,
+            y_offset: 0,
+            pixels,
+            delay_num,
+            delay_den,
+            dispose_op: 0,
+            blend_op: 0,
+        }
+    }
+    /// Checks that the frame has pixel data for its whole region, that the region fits on the canvas,
+    /// and that the dispose and blend ops are valid.
+    fn validate(&self, index: usize, canvas_width: u32, canvas_height: u32) -> Result<(), PngError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(PngError::InvalidData(
+                format!("Invalid frame dimensions. Frame #: {index} width: {} height: {}", self.width, self.height),
+            ));
+        }
+
+        let fits_width = self.x_offset.checked_add(self.width).is_some_and(|right| right <= canvas_width);
+        let fits_height = self.y_offset.checked_add(self.height).is_some_and(|bottom| bottom <= canvas_height);
+        if !fits_width || !fits_height {
+            return Err(PngError::InvalidData(
+                format!("Frame does not fit within the canvas. Frame #: {index} \
+                frame: {}x{} at ({}, {}) canvas: {canvas_width}x{canvas_height}",
+                        self.width,
+                        self.height,
+                        self.x_offset,
+                        self.y_offset,
+                ),
+            ));
+        }
+
+        let expected_len = self.width as usize * self.height as usize * RGBA_PIXEL_SIZE;
+        if self.pixels.len() != expected_len {
+            return Err(PngError::InvalidData(
+                format!("Frame pixel data is the wrong size. Frame #: {index} expected: {expected_len} got: {}",
+                        self.pixels.len(),
+                ),
+            ));
+        }
+
+        if self.dispose_op > MAX_DISPOSE_OP {
+            return Err(PngError::InvalidData(
+                format!("Invalid dispose op. Must be 0-{MAX_DISPOSE_OP}. Frame #: {index} dispose op: {}", self.dispose_op),
+            ));
+        }
+
+        if self.blend_op > MAX_BLEND_OP {
+            return Err(PngError::InvalidData(
+                format!("Invalid blend op. Must be 0-{MAX_BLEND_OP}. Frame #: {index} blend op: {}", self.blend_op),
+            ));
+        }
+
+        Ok(())
+    }
+    /// Creates the fcTL chunk for this frame.
+    fn frame_control(&self, sequence_number: u32) -> Result<PNGChunk, PngError> {
+        FCTL::new(
+            sequence_number,
+            self.width,
+            self.height,
+            self.x_offset,
+            self.y_offset,
+            self.delay_num,
+            self.delay_den,
+            self.dispose_op,
+            self.blend_op,
+        )
+    }
+    /// Adds a filter byte (type 0, none) to the start of every row and compresses the result.
+    fn compress(&self) -> Result<Vec<u8>, PngError> {
+        let row_len = self.width as usize * RGBA_PIXEL_SIZE;
+        let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+        for row in self.pixels.chunks_exact(row_len) {
+            encoder.write_all(&[0])?;
+            encoder.write_all(row)?;
+        }
+
+        Ok(encoder.finish()?)
+    }
+}
+
+impl PNGBuilder<'_> {
+    /// Builds an animated PNG with a canvas of the provided width and height, where each frame is 8-bit
+    /// RGBA. The first frame is also the default image, so it must cover the whole canvas. The
+    /// animation loops forever.
+    ///
+    /// Emits IHDR, acTL, the fcTL and IDAT chunks of the first frame, an fcTL and fdAT chunk for each
+    /// following frame, and then IEND.
+    pub fn animation(width: u32, height: u32, frames: Vec<FrameSpec>) -> Result<Vec<u8>, PngError> {
+        if width == 0 || height == 0 || width > i32::MAX as u32 || height > i32::MAX as u32 {
+            return Err(PngError::InvalidData(
+                format!("Invalid canvas dimensions. width: {width} height: {height}"),
+            ));
+        }
+
+        let first_frame = frames.first().ok_or_else(|| {
+            PngError::InvalidData("Animation must have at least one frame".to_string())
+        })?;
+        if first_frame.x_offset != 0 || first_frame.y_offset != 0
+            || first_frame.width != width || first_frame.height != height {
+            return Err(PngError::InvalidData(
+                "The first frame of an animation must cover the whole canvas".to_string(),
+            ));
+        }
+
+        for (i, frame) in frames.iter().enumerate() {
+            frame.validate(i, width, height)?;
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend(width.to_be_bytes());
+        header.extend(height.to_be_bytes());
+        // 8-bit truecolor with alpha, deflate, adaptive filtering, no interlace
+        header.extend([8, 6, 0, 0, 0]);
+
+        let mut builder = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &header)?)
+            // 0 plays means the animation loops forever
+            .with_chunk(ACTL::new(frames.len() as u32, 0)?);
+
+        // fcTL and fdAT chunks share one sequence, starting at 0.
+        let mut sequence_number = 0;
+        for (i, frame) in frames.iter().enumerate() {
+            builder = builder.with_chunk(frame.frame_control(sequence_number)?);
+            sequence_number += 1;
+
+            let image_data = frame.compress()?;
+            if i == 0 {
+                builder = builder.with_chunk(PNGChunk::new("IDAT", &image_data)?);
+            } else {
+                let mut frame_data = Vec::with_capacity(4 + image_data.len());
+                frame_data.extend(sequence_number.to_be_bytes());
+                frame_data.extend(image_data);
+                builder = builder.with_chunk(PNGChunk::new("fdAT", &frame_data)?);
+                sequence_number += 1;
+            }
+        }
+
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::PNGBuilder;
+    use crate::encode::FrameSpec;
+    use crate::PNGReader;
+
+    #[test]
+    fn animation() {
+        let red = [255, 0, 0, 255].repeat(4);
+        let mut blue = FrameSpec::new(1, 1, vec![0, 0, 255, 255], 1, 10);
+        blue.x_offset = 1;
+        blue.y_offset = 1;
+        blue.blend_op = 1;
+        let frames = vec![
+            FrameSpec::new(2, 2, red, 1, 10),
+            blue,
+            FrameSpec::new(2, 2, vec![0; 16], 1, 0),
+        ];
+
+        let png_file = PNGBuilder::animation(2, 2, frames).expect("Could not build APNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let chunk_types: Vec<&str> = png
+            .get_all_chunk_info()
+            .iter()
+            .map(|chunk| chunk.get_chunk_type())
+            .collect();
+        assert_eq!(
+            chunk_types,
+            ["IHDR", "acTL", "fcTL", "IDAT", "fcTL", "fdAT", "fcTL", "fdAT", "IEND"]
+        );
+
+        let animation_control = png.get_chunk_of_type("acTL").unwrap();
+        assert_eq!(animation_control.get_chunk_data(), &[0, 0, 0, 3, 0, 0, 0, 0]);
+
+        let sequence_numbers: Vec<u32> = png
+            .get_all_chunk_info()
+            .iter()
+            .filter(|chunk| matches!(chunk.get_chunk_type(), "fcTL" | "fdAT"))
+            .map(|chunk| u32::from_be_bytes(chunk.get_chunk_data()[..4].try_into().unwrap()))
+            .collect();
+        assert_eq!(sequence_numbers, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn animation_frame_outside_canvas() {
+        let mut frame = FrameSpec::new(2, 2, vec![0; 16], 1, 10);
+        frame.x_offset = 1;
+        let frames = vec![FrameSpec::new(2, 2, vec![0; 16], 1, 10), frame];
+
+        assert!(PNGBuilder::animation(2, 2, frames).is_err());
+    }
+
+    #[test]
+    fn animation_first_frame_must_cover_canvas() {
+        let frames = vec![FrameSpec::new(1, 1, vec![0; 4], 1, 10)];
+
+        assert!(PNGBuilder::animation(2, 2, frames).is_err());
+        assert!(PNGBuilder::animation(2, 2, vec![]).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// The ways that reading, validating or building a PNG file can fail. Every fallible public function in
+/// the crate returns this error. The only exception is the `std::io::Read` impl of `IdatReader`, which
+/// has to return `std::io::Error`. With the `std` feature, it converts into a `std::io::Error`, so it can
+/// be used with `?` in functions that return `std::io::Result`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PngError {
+    /// The buffer does not start with the PNG signature.
+    BadSignature,
+    /// The buffer ended before the end of the data that was expected. `offset` is where the data starts
+    /// in the file, `expected` is the number of bytes that were needed, and `got` is the number of bytes
+    /// that were left.
+    TruncatedBuffer {
+        offset: usize,
+        expected: usize,
+        got: usize,
+    },
+    /// The crc stored in a chunk does not match the crc calculated from its chunk type and chunk data.
+    CrcMismatch {
+        chunk_type: String,
+        index: usize,
+        offset: usize,
+        stored: u32,
+        computed: u32,
+    },
+    /// Every crc that failed, sorted by chunk index. Each error is a `PngError::CrcMismatch`.
+    CrcMismatches(Vec<PngError>),
+    /// The chunk type is not 4 ASCII letters. `offset` is where the chunk starts in the file, if the chunk
+    /// type came from a file.
+    InvalidChunkType { offset: Option<usize>, reason: String },
+    /// The chunk data is too long. The max chunk data length is 0x7FFFFFFF bytes. `offset` is where the
+    /// chunk starts in the file, if the length came from a file.
+    ChunkTooLong { offset: Option<usize>, len: usize },
+    /// The IHDR chunk is missing, is the wrong size, or has an invalid field value.
+    InvalidIhdr(String),
+    /// A chunk or field holds a value that is not allowed. The message describes the problem.
+    InvalidData(String),
+    /// The data needs a feature that is not enabled, like compressed text without `text-decode`.
+    Unsupported(String),
+    /// An error from a reader, a writer or the file system.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl Display for PngError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PngError::BadSignature => write!(f, "Buffer does not start with a valid PNG signature"),
+            PngError::TruncatedBuffer {
+                offset,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Unexpected end of data. Offset: {offset:X} Expected len: {expected:X} Bytes remaining: {got:X}"
+            ),
+            PngError::CrcMismatch {
+                chunk_type,
+                index,
+                offset,
+                stored,
+                computed,
+            } => write!(
+                f,
+                "CRC failed. Chunk #: {index} Offset: {offset:X} Chunk type: {chunk_type}, Chunk crc: {stored:X}, Calculated crc: {computed:X}"
+            ),
+            PngError::CrcMismatches(errors) => {
+                write!(f, "Chunk Validation Errors:")?;
+                for err in errors {
+                    write!(f, "\n{err}")?;
+                }
+                Ok(())
+            }
+            PngError::InvalidChunkType {
+                offset: Some(offset),
+                reason,
+            } => write!(f, "Invalid chunk type. Offset: {offset:X} {reason}"),
+            PngError::InvalidChunkType { offset: None, reason } => write!(f, "Invalid chunk type. {reason}"),
+            PngError::ChunkTooLong { offset: Some(offset), len } => {
+                write!(f, "Chunk data is too long. Offset: {offset:X}\nMax: 0x7FFFFFFF\nLen: 0x{len:08X}")
+            }
+            PngError::ChunkTooLong { offset: None, len } => {
+                write!(f, "Chunk data is too long.\nMax: 0x7FFFFFFF\nLen: 0x{len:08X}")
+            }
+            PngError::InvalidIhdr(msg) => write!(f, "{msg}"),
+            PngError::InvalidData(msg) => write!(f, "{msg}"),
+            PngError::Unsupported(msg) => write!(f, "{msg}"),
+            #[cfg(feature = "std")]
+            PngError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl PngError {
+    /// Gets the offset in the file where the chunk that caused the error starts, or where the missing data
+    /// should have been, for errors that came from reading a file.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            PngError::TruncatedBuffer { offset, .. } => Some(*offset),
+            PngError::CrcMismatch { offset, .. } => Some(*offset),
+            PngError::CrcMismatches(errors) => errors.first().and_then(PngError::offset),
+            PngError::InvalidChunkType { offset, .. } => *offset,
+            PngError::ChunkTooLong { offset, .. } => *offset,
+            _ => None,
+        }
+    }
+}
+
+impl core::error::Error for PngError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            PngError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<PngError> for std::io::Error {
+    fn from(err: PngError) -> Self {
+        use std::io::ErrorKind;
+
+        match err {
+            PngError::Io(e) => e,
+            PngError::TruncatedBuffer { .. } => std::io::Error::new(ErrorKind::UnexpectedEof, err),
+            PngError::Unsupported(_) => std::io::Error::new(ErrorKind::Unsupported, err),
+            _ => std::io::Error::new(ErrorKind::InvalidData, err),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl From<std::io::Error> for PngError {
+    fn from(err: std::io::Error) -> Self {
+        PngError::Io(err)
+    }
+}
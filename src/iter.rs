@@ -1,22 +1,44 @@
 use crate::chunk::crc::ChunkCRC;
 use crate::chunk::header::ChunkHeader;
 use crate::chunk::refs::ChunkRefs;
-use crate::consts::PNG_SIGNATURE_LENGTH;
+use crate::consts::{MAX_CHUNK_LENGTH, PNG_SIGNATURE_LENGTH};
 use crate::PNGReader;
-use buffer_reader::BufferReader;
+use bytemuck::AnyBitPattern;
+#[cfg(feature = "std")]
+use std::io::Read;
 
 pub struct Iter<'a> {
-    buffer: BufferReader<'a>,
+    buffer: &'a [u8],
     current_section: [u8; 4],
 }
 
+impl<'a> Iter<'a> {
+    /// Splits `len` bytes off of the front of the buffer. Returns `None` if there are not enough bytes left.
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.buffer.len() {
+            return None;
+        }
+
+        let (bytes, rest) = self.buffer.split_at(len);
+        self.buffer = rest;
+
+        Some(bytes)
+    }
+    /// Gets a reference to a `T` at the front of the buffer, and then advances the buffer past it.
+    fn read_t<T: AnyBitPattern>(&mut self) -> Option<&'a T> {
+        let bytes = self.read_bytes(core::mem::size_of::<T>())?;
+
+        bytemuck::try_from_bytes(bytes).ok()
+    }
+}
+
 impl<'a> IntoIterator for PNGReader<'a> {
     type Item = ChunkRefs<'a>;
     type IntoIter = Iter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
         Iter {
-            buffer: BufferReader::new(&self.buffer[PNG_SIGNATURE_LENGTH..]),
+            buffer: &self.buffer[PNG_SIGNATURE_LENGTH..],
             current_section: [0; 4],
         }
     }
@@ -28,7 +50,7 @@ impl<'a> IntoIterator for &PNGReader<'a> {
 
     fn into_iter(self) -> Self::IntoIter {
         Iter {
-            buffer: BufferReader::new(&self.buffer[PNG_SIGNATURE_LENGTH..]),
+            buffer: &self.buffer[PNG_SIGNATURE_LENGTH..],
             current_section: [0; 4],
         }
     }
@@ -37,13 +59,16 @@ impl<'a> IntoIterator for &PNGReader<'a> {
 impl<'a> Iterator for Iter<'a> {
     type Item = ChunkRefs<'a>;
 
+    /// Gets the next chunk, up to and including the IEND chunk. Iteration stops early, without an error,
+    /// at a chunk with an invalid chunk type, a length over `MAX_CHUNK_LENGTH`, or data that runs past
+    /// the end of the buffer. `PNGReader::validate_chunk_bounds` reports these as errors instead.
     fn next(&mut self) -> Option<Self::Item> {
         if &self.current_section == b"IEND" {
             return None;
         }
 
         // Get a reference to the chunk header and then advance the buffer to the start of the chunk
-        let chunk = self.buffer.read_t::<ChunkHeader>().ok()?;
+        let chunk = self.read_t::<ChunkHeader>()?;
         chunk.validate_chunk_type().ok()?;
 
         // Keep track of the current section so that we can check the next time we call `next()`
@@ -51,12 +76,49 @@ impl<'a> Iterator for Iter<'a> {
 
         // Get the length of the chunk data from the header, get a slice containing the chunk data,
         // and then advance the buffer to the start of the crc data.
+        if chunk.get_length() > MAX_CHUNK_LENGTH {
+            return None;
+        }
         let chunk_data_len = chunk.get_length() as usize;
-        let chunk_data = self.buffer.read_bytes(chunk_data_len).ok()?;
+        let chunk_data = self.read_bytes(chunk_data_len)?;
 
         // Get a reference to the crc value and then advance the buffer to the start of the next chunk.
-        let crc = self.buffer.read_t::<ChunkCRC>().ok()?;
+        let crc = self.read_t::<ChunkCRC>()?;
 
         Some(ChunkRefs::new(chunk, chunk_data, crc))
     }
 }
+
+/// A reader over the compressed image data stream. Reads through the data of each IDAT chunk in order,
+/// as if the IDAT chunks were one contiguous buffer.
+#[cfg(feature = "std")]
+pub struct IdatReader<'a> {
+    chunks: Iter<'a>,
+    current_data: &'a [u8],
+}
+
+#[cfg(feature = "std")]
+impl<'a> IdatReader<'a> {
+    pub fn new(png: &PNGReader<'a>) -> Self {
+        IdatReader {
+            chunks: png.into_iter(),
+            current_data: &[],
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Read for IdatReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        // Move on to the next IDAT chunk once the current one is used up. There may be empty IDAT
+        // chunks, so keep going until there is data to read, or there are no IDAT chunks left.
+        while self.current_data.is_empty() {
+            match self.chunks.find(|chunk| chunk.get_chunk_type() == "IDAT") {
+                Some(chunk) => self.current_data = chunk.get_chunk_data(),
+                None => return Ok(0),
+            }
+        }
+
+        self.current_data.read(buf)
+    }
+}
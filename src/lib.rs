@@ -1,11 +1,45 @@
-use crate::consts::{PNG_SIGNATURE, PNG_SIGNATURE_LENGTH};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use crate::consts::{
+    ALWAYS_ALLOWED_CHUNK_TYPES, CHUNK_CRC_SIZE, CHUNK_HEADER_SIZE, MAX_CHUNK_LENGTH, PNG_SIGNATURE,
+    PNG_SIGNATURE_LENGTH,
+};
+use chunk::header::ChunkHeader;
 use chunk::refs::ChunkRefs;
-use std::io::{Error, ErrorKind};
+use chunk::ty::ancillary::exif::EXIF;
+use chunk::ty::ancillary::splt::SPLT;
+use chunk::ty::critical::ihdr::{IHDRDetails, IHDR};
+use error::PngError;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use builder::PNGBuilder;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
 
+#[cfg(feature = "std")]
+pub mod apng;
+#[cfg(feature = "std")]
 pub mod builder;
 pub mod chunk;
 pub mod consts;
+#[cfg(feature = "std")]
+pub mod document;
+#[cfg(feature = "decode")]
+pub mod decode;
+#[cfg(feature = "encode")]
+pub mod encode;
+pub mod error;
 pub mod iter;
+pub mod placement;
+pub mod reader_mut;
+pub mod text;
 
 /// A Rust type that is able to enumerate and inspect a buffer that is a valid PNG file.
 pub struct PNGReader<'a> {
@@ -16,7 +50,7 @@ impl<'a> PNGReader<'a> {
     /// Creates a new PNG file and then validates the contents of the png header and each chunk in the
     /// png. This will calculate the crc of every chunk, so it may take some time, if your png contains
     /// large chunks.
-    pub fn new(buffer: &'a [u8]) -> std::io::Result<Self> {
+    pub fn new(buffer: &'a [u8]) -> Result<Self, PngError> {
         let png = PNGReader { buffer };
 
         png.validate_header()?;
@@ -29,7 +63,7 @@ impl<'a> PNGReader<'a> {
     /// # Safety
     ///
     /// The user should at least call `PNG::validate_png()` after creating the new PNG object. This
-    /// will at least check that the header is correct. The user can also call the `ChunkInfo::validate_crc()`
+    /// will at least check that the header is correct. The user can also call the `ChunkRefs::validate_crc()`
     /// method on each individual chunk, to validate the crcs of the chunks the user cares about.
     pub unsafe fn new_unchecked(buffer: &'a [u8]) -> Self {
         PNGReader { buffer }
@@ -43,72 +77,803 @@ impl<'a> PNGReader<'a> {
             .collect()
     }
     pub fn get_all_chunk_info(&self) -> Vec<ChunkRefs<'a>> {
-        self.into_iter().collect()
+        let mut chunks = Vec::with_capacity(self.chunk_count());
+        chunks.extend(self);
+        chunks
+    }
+    /// Counts the chunks in the file, including the IEND chunk, with one pass over the chunk headers. The
+    /// chunk data is skipped over, so this is cheap even for large files.
+    pub fn chunk_count(&self) -> usize {
+        self.into_iter().count()
+    }
+    /// Iterates over the chunks in the file from the last chunk to the first. The chunks are found with
+    /// one forward pass first, so this yields the same chunks as the forward iterator, in reverse order,
+    /// starting from the IEND chunk. Anything after the IEND chunk is ignored, like in forward iteration.
+    pub fn chunks_rev(&self) -> impl DoubleEndedIterator<Item = ChunkRefs<'a>> {
+        self.get_all_chunk_info().into_iter().rev()
+    }
+    /// Iterates over the ancillary chunks in the file, which are the chunks a decoder can safely ignore,
+    /// like tEXt or tIME. See `ChunkType::is_ancillary`.
+    pub fn ancillary_chunks(&self) -> impl Iterator<Item = ChunkRefs<'a>> {
+        self.into_iter().filter(|chunk| chunk.is_ancillary())
+    }
+    /// Iterates over the critical chunks in the file, which are the chunks needed to display the image,
+    /// like IHDR, PLTE, IDAT and IEND. See `ChunkType::is_ancillary`.
+    pub fn critical_chunks(&self) -> impl Iterator<Item = ChunkRefs<'a>> {
+        self.into_iter().filter(|chunk| !chunk.is_ancillary())
+    }
+    /// Iterates over the chunks that an editor may copy to a modified file, even if it does not know the
+    /// chunk type. See `ChunkType::is_safe_to_copy`.
+    pub fn safe_to_copy_chunks(&self) -> impl Iterator<Item = ChunkRefs<'a>> {
+        self.into_iter().filter(|chunk| chunk.is_safe_to_copy())
+    }
+    /// Iterates over the chunks in the file, stopping before the first chunk that would take the total
+    /// number of bytes read past `max_bytes`. Each chunk counts for its full size, including the chunk
+    /// header and crc. The PNG signature is not counted.
+    pub fn iter_within(&self, max_bytes: usize) -> impl Iterator<Item = ChunkRefs<'a>> {
+        self.into_iter().scan(0usize, move |consumed, chunk| {
+            *consumed = consumed
+                .saturating_add(CHUNK_HEADER_SIZE + chunk.get_length() as usize + CHUNK_CRC_SIZE);
+
+            if *consumed > max_bytes {
+                return None;
+            }
+
+            Some(chunk)
+        })
+    }
+    /// Returns the slice of the buffer starting at the first chunk of type `from_type` up to, but not
+    /// including, the first chunk of type `to_type` that comes after it. Returns `None` if either chunk
+    /// type is missing, or if there is no `to_type` chunk after the `from_type` chunk.
+    pub fn bytes_between(&self, from_type: &str, to_type: &str) -> Option<&'a [u8]> {
+        let mut chunks = self.chunks_with_offsets();
+        let (start, _) = chunks.find(|(_, chunk)| chunk.get_chunk_type() == from_type)?;
+        let (end, _) = chunks.find(|(_, chunk)| chunk.get_chunk_type() == to_type)?;
+
+        Some(&self.buffer[start..end])
+    }
+    /// Finds the sPLT chunk with the provided palette name and returns a view over it. Returns `None` if
+    /// there is no sPLT chunk with that name. Palette names are Latin-1, so each byte of the name is
+    /// compared to the matching character in `name`. sPLT chunks that are malformed are skipped.
+    pub fn suggested_palette(&self, name: &str) -> Option<SPLT<'a>> {
+        self.into_iter()
+            .filter_map(|chunk| SPLT::from_chunk_refs(&chunk))
+            .find(|palette| palette.get_name().iter().map(|&b| b as char).eq(name.chars()))
+    }
+    /// Gets the raw Exif data from the first eXIf chunk, without parsing it. Returns `None` if there is no
+    /// eXIf chunk.
+    pub fn exif(&self) -> Option<&'a [u8]> {
+        let chunk = self.get_chunk_of_type("eXIf")?;
+
+        EXIF::from_chunk_refs(&chunk).map(|exif| exif.get_exif_data())
+    }
+    /// Returns the number of entries in the PLTE chunk. Returns `None` if there is no PLTE chunk, or if
+    /// its length is not a non-zero multiple of 3.
+    pub fn palette_size(&self) -> Option<usize> {
+        let plte_chunk = self.get_chunk_of_type("PLTE")?;
+        let len = plte_chunk.get_length() as usize;
+        if len == 0 || !len.is_multiple_of(3) {
+            return None;
+        }
+
+        Some(len / 3)
+    }
+    /// Finds the IHDR chunk, and returns a validated copy of it. Fails if there is no IHDR chunk, or if the
+    /// IHDR chunk is the wrong size or fails validation.
+    pub(crate) fn get_validated_header(&self) -> Result<IHDR, PngError> {
+        let ihdr_chunk = self
+            .get_chunk_of_type("IHDR")
+            .ok_or_else(|| PngError::InvalidIhdr("PNG does not contain an IHDR chunk".to_string()))?;
+        let header = *IHDR::from_chunk_refs(&ihdr_chunk)
+            .ok_or_else(|| PngError::InvalidIhdr("IHDR chunk is not a valid size".to_string()))?;
+        header.validate()?;
+
+        Ok(header)
+    }
+    /// Gets the width and height of the image from the IHDR chunk. Fails if there is no IHDR chunk, or if
+    /// the IHDR chunk is the wrong size or fails validation.
+    pub fn dimensions(&self) -> Result<(i32, i32), PngError> {
+        let header = self.get_validated_header()?;
+
+        Ok((header.get_width(), header.get_height()))
+    }
+    /// Gets the color type of the image from the IHDR chunk. Fails like `dimensions`.
+    pub fn color_type(&self) -> Result<u8, PngError> {
+        Ok(self.get_validated_header()?.get_details().get_color_type())
+    }
+    /// Gets the bit depth of the image from the IHDR chunk. Fails like `dimensions`.
+    pub fn bit_depth(&self) -> Result<u8, PngError> {
+        Ok(self.get_validated_header()?.get_details().get_bit_depth())
+    }
+    /// Returns true if the image is grayscale, with or without alpha. Fails like `dimensions`.
+    pub fn is_grayscale(&self) -> Result<bool, PngError> {
+        Ok(self.get_validated_header()?.get_details().is_grayscale())
+    }
+    /// Returns true if the color type has an alpha channel. Transparency from a tRNS chunk is not counted.
+    /// Fails like `dimensions`.
+    pub fn has_alpha(&self) -> Result<bool, PngError> {
+        Ok(self.get_validated_header()?.get_details().has_alpha())
+    }
+    /// Returns true if the image stores palette indices, and needs a PLTE chunk. Fails like `dimensions`.
+    pub fn uses_palette(&self) -> Result<bool, PngError> {
+        Ok(self.get_validated_header()?.get_details().uses_palette())
+    }
+    /// Iterates over the chunks in the file, along with the offset of the start of each chunk in the
+    /// buffer.
+    pub fn chunks_with_offsets(&self) -> impl Iterator<Item = (usize, ChunkRefs<'a>)> {
+        self.into_iter().scan(PNG_SIGNATURE_LENGTH, |offset, chunk| {
+            let chunk_offset = *offset;
+            *offset += CHUNK_HEADER_SIZE + chunk.get_length() as usize + CHUNK_CRC_SIZE;
+
+            Some((chunk_offset, chunk))
+        })
+    }
+    /// Returns the range of the buffer that holds the first chunk of the provided type, from the length
+    /// field through the crc. Returns `None` if there is no chunk of that type.
+    pub fn chunk_range(&self, chunk_type: &str) -> Option<Range<usize>> {
+        self.chunks_with_offsets()
+            .find(|(_, chunk)| chunk.get_chunk_type() == chunk_type)
+            .map(|(offset, chunk)| chunk_range_at(offset, &chunk))
+    }
+    /// Returns the range of the buffer that holds each chunk of the provided type, in order, from the
+    /// length field through the crc.
+    pub fn chunk_ranges(&self, chunk_type: &str) -> Vec<Range<usize>> {
+        self.chunks_with_offsets()
+            .filter(|(_, chunk)| chunk.get_chunk_type() == chunk_type)
+            .map(|(offset, chunk)| chunk_range_at(offset, &chunk))
+            .collect()
+    }
+}
+
+/// Gets the range of the buffer that the chunk at the provided offset takes up, including the chunk header
+/// and crc.
+fn chunk_range_at(offset: usize, chunk: &ChunkRefs) -> Range<usize> {
+    offset..offset + CHUNK_HEADER_SIZE + chunk.get_length() as usize + CHUNK_CRC_SIZE
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF29CE484222325;
+const FNV_PRIME: u64 = 0x100000001B3;
+/// Adds the bytes to a 64-bit FNV-1a hash.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    hash
+}
+/// Checks that a PLTE chunk is present for color type 3, and absent for the grayscale color types, 0 and
+/// 4. A PLTE chunk is optional for color types 2 and 6.
+fn validate_palette_presence(details: &IHDRDetails, has_palette: bool) -> Result<(), PngError> {
+    let color_type = details.get_color_type();
+    if details.uses_palette() && !has_palette {
+        return Err(PngError::InvalidData(
+            "Color type 3 requires a PLTE chunk".to_string(),
+        ));
+    }
+    if details.is_grayscale() && has_palette {
+        return Err(PngError::InvalidData(
+            format!("PLTE chunk must not appear for grayscale images. Color type: {color_type}"),
+        ));
+    }
+
+    Ok(())
+}
+/// Builds a `PngError::CrcMismatch` for the chunk at the provided index and offset.
+fn crc_mismatch(index: usize, offset: usize, chunk_info: &ChunkRefs) -> PngError {
+    PngError::CrcMismatch {
+        chunk_type: chunk_info.get_chunk_type().to_string(),
+        index,
+        offset,
+        stored: chunk_info.get_crc(),
+        computed: chunk_info.calculate_crc(),
+    }
+}
+/// Turns the crc failures from `validate_chunks` or `validate_chunks_parallel` into a result. A single
+/// failure is returned as is, and more than one is returned as `PngError::CrcMismatches`.
+fn crc_result(mut failures: Vec<PngError>) -> Result<(), PngError> {
+    match failures.len() {
+        0 => Ok(()),
+        1 => Err(failures.remove(0)),
+        _ => Err(PngError::CrcMismatches(failures)),
     }
 }
 
 impl PNGReader<'_> {
     /// Checks that the provided buffer has a valid PNG signature. Returns an error if the buffer is
     /// not long enough or the magic bytes at the start of the file are not the correct PNG signature.
-    pub fn validate_header(&self) -> std::io::Result<()> {
+    pub fn validate_header(&self) -> Result<(), PngError> {
         if self.buffer.len() < PNG_SIGNATURE_LENGTH {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                format!("Buffer is shorter than PNG signature length: {PNG_SIGNATURE_LENGTH} buffer len: {}", self.buffer.len()),
-            ));
+            return Err(PngError::TruncatedBuffer {
+                offset: 0,
+                expected: PNG_SIGNATURE_LENGTH,
+                got: self.buffer.len(),
+            });
         }
 
         if self.buffer[..PNG_SIGNATURE_LENGTH] != PNG_SIGNATURE {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                "Buffer does not start with a valid PNG signature",
-            ));
+            return Err(PngError::BadSignature);
         }
 
         Ok(())
     }
-    /// Iterates through all chunks in the PNG file and checks that the crc listed in the chunk is valid.
-    /// If any of the chunks fail, this method returns an error with each chunk and the index that failed.
-    pub fn validate_chunks(&self) -> std::io::Result<()> {
-        let mut err = String::new();
+    /// Walks the chunk framing from the end of the signature to the IEND chunk, and checks that each
+    /// chunk fits in the buffer and has a valid chunk type. Returns `PngError::TruncatedBuffer` if a chunk
+    /// is cut off, or if the buffer ends before an IEND chunk, so that truncated files are not mistaken
+    /// for files that just end early.
+    pub fn validate_chunk_bounds(&self) -> Result<(), PngError> {
+        let mut offset = PNG_SIGNATURE_LENGTH;
 
-        for (i, chunk_info) in self.into_iter().enumerate() {
-            if !chunk_info.validate_crc() {
-                err.push_str(&format!("CRC failed. Chunk #: {i} Chunk type: {}, Chunk length: {:X}, Chunk crc: {:X}, Calculated crc: {:X}",
-                                      chunk_info.get_chunk_type(),
-                                      chunk_info.get_length(),
-                                      chunk_info.get_crc(),
-                                      chunk_info.get_crc()),
-                );
-                err.push('\n');
+        for i in 0.. {
+            let remaining = &self.buffer[offset..];
+            if remaining.len() < CHUNK_HEADER_SIZE {
+                return Err(PngError::TruncatedBuffer {
+                    offset,
+                    expected: CHUNK_HEADER_SIZE,
+                    got: remaining.len(),
+                });
+            }
+
+            let header = unsafe { &*(remaining.as_ptr() as *const ChunkHeader) };
+            header.validate_chunk_type().map_err(|e| match e {
+                PngError::InvalidChunkType { reason, .. } => PngError::InvalidChunkType {
+                    offset: Some(offset),
+                    reason: format!("Chunk #: {i} {reason}"),
+                },
+                e => e,
+            })?;
+            if header.get_length() > MAX_CHUNK_LENGTH {
+                return Err(PngError::ChunkTooLong {
+                    offset: Some(offset),
+                    len: header.get_length() as usize,
+                });
+            }
+
+            let chunk_len = CHUNK_HEADER_SIZE + header.get_length() as usize + CHUNK_CRC_SIZE;
+            if chunk_len > remaining.len() {
+                return Err(PngError::TruncatedBuffer {
+                    offset,
+                    expected: chunk_len,
+                    got: remaining.len(),
+                });
             }
+
+            if header.get_chunk_type_as_str() == "IEND" {
+                break;
+            }
+            offset += chunk_len;
+        }
+
+        Ok(())
+    }
+    /// Iterates through all chunks in the PNG file and checks that the crc listed in the chunk is valid.
+    /// Every chunk is checked. If one chunk fails, returns `PngError::CrcMismatch` for it, and if more than
+    /// one fails, returns all of them as `PngError::CrcMismatches`, sorted by chunk index. A chunk that is
+    /// cut off by the end of the buffer is reported by `validate_chunk_bounds` first, as
+    /// `PngError::TruncatedBuffer`, instead of as a crc failure.
+    pub fn validate_chunks(&self) -> Result<(), PngError> {
+        self.validate_chunk_bounds()?;
+
+        let failures: Vec<PngError> = self
+            .chunks_with_offsets()
+            .enumerate()
+            .filter(|(_, (_, chunk_info))| !chunk_info.validate_crc())
+            .map(|(i, (offset, chunk_info))| crc_mismatch(i, offset, &chunk_info))
+            .collect();
+
+        crc_result(failures)
+    }
+    /// Like `validate_chunks`, but checks the crcs of the chunks in parallel. Every chunk is checked, and
+    /// the failures are reported the same way as `validate_chunks`.
+    #[cfg(feature = "rayon")]
+    pub fn validate_chunks_parallel(&self) -> Result<(), PngError> {
+        use rayon::prelude::*;
+
+        self.validate_chunk_bounds()?;
+
+        let chunks: Vec<(usize, ChunkRefs)> = self.chunks_with_offsets().collect();
+        // `collect` keeps the original order, so the failures stay sorted by chunk index.
+        let failures: Vec<PngError> = chunks
+            .par_iter()
+            .enumerate()
+            .filter(|(_, (_, chunk_info))| !chunk_info.validate_crc())
+            .map(|(i, (offset, chunk_info))| crc_mismatch(i, *offset, chunk_info))
+            .collect();
+
+        crc_result(failures)
+    }
+    /// Checks the minimum requirements the PNG spec places on a datastream, which any conformant decoder
+    /// relies on: a valid signature, exactly one valid IHDR chunk as the first chunk, at least one IDAT
+    /// chunk, and an IEND chunk as the last chunk. A PLTE chunk is required for color type 3 and forbidden
+    /// for color types 0 and 4. Chunk ordering beyond that is not checked. Returns an error describing the
+    /// first requirement that is not met.
+    pub fn is_minimal_compliant(&self) -> Result<(), PngError> {
+        self.validate_header()?;
+
+        let chunks = self.get_all_chunk_info();
+        let first = chunks
+            .first()
+            .ok_or_else(|| PngError::InvalidData("PNG does not contain any chunks".to_string()))?;
+        if first.get_chunk_type() != "IHDR" {
+            return Err(PngError::InvalidData(
+                format!("First chunk must be IHDR. First chunk type: {}", first.get_chunk_type()),
+            ));
+        }
+        let header = IHDR::from_chunk_refs(first)
+            .ok_or_else(|| PngError::InvalidData("IHDR chunk is not a valid size".to_string()))?;
+        header.validate()?;
+
+        let count = |chunk_type: &str| chunks.iter().filter(|c| c.get_chunk_type() == chunk_type).count();
+        let ihdr_count = count("IHDR");
+        if ihdr_count != 1 {
+            return Err(PngError::InvalidData(
+                format!("PNG must contain exactly one IHDR chunk. IHDR count: {ihdr_count}"),
+            ));
+        }
+        if count("IDAT") == 0 {
+            return Err(PngError::InvalidData(
+                "PNG must contain at least one IDAT chunk".to_string(),
+            ));
+        }
+
+        let last = chunks.last().map(|c| c.get_chunk_type()).unwrap_or_default();
+        if last != "IEND" {
+            return Err(PngError::InvalidData(
+                format!("Last chunk must be IEND. Last chunk type: {last}"),
+            ));
         }
 
-        if !err.is_empty() {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                format!("Chunk Validation Errors:\n{err}"),
+        validate_palette_presence(header.get_details(), count("PLTE") != 0)
+    }
+    /// Checks that the chunks every PNG needs are present: a valid IHDR chunk, at least one IDAT chunk and
+    /// an IEND chunk, plus a PLTE chunk for color type 3. A PLTE chunk must not be present for the
+    /// grayscale color types, 0 and 4. Only presence is checked, not order or count, see
+    /// `is_minimal_compliant` for the stricter check.
+    pub fn validate_required_chunks(&self) -> Result<(), PngError> {
+        let header = self.get_validated_header()?;
+        if self.get_chunk_of_type("IDAT").is_none() {
+            return Err(PngError::InvalidData(
+                "PNG must contain at least one IDAT chunk".to_string(),
+            ));
+        }
+        if self.get_chunk_of_type("IEND").is_none() {
+            return Err(PngError::InvalidData(
+                "PNG must contain an IEND chunk".to_string(),
+            ));
+        }
+
+        validate_palette_presence(header.get_details(), self.get_chunk_of_type("PLTE").is_some())
+    }
+    /// Checks that the file has an IEND chunk, that the IEND chunk is empty, and that no chunk follows it.
+    /// Iteration stops at the first IEND chunk, so any chunk after it would otherwise be ignored. Bytes
+    /// after the IEND chunk that do not start with a valid chunk header, like the zero padding added by
+    /// `PNGBuilder::build_aligned`, are allowed.
+    pub fn validate_iend(&self) -> Result<(), PngError> {
+        let (offset, iend) = self
+            .chunks_with_offsets()
+            .find(|(_, chunk)| chunk.get_chunk_type() == "IEND")
+            .ok_or_else(|| PngError::InvalidData("PNG does not contain an IEND chunk".to_string()))?;
+        if iend.get_length() != 0 {
+            return Err(PngError::InvalidData(
+                format!("IEND chunk must be empty. Offset: {offset:X} Length: {}", iend.get_length()),
             ));
         }
 
+        let end = chunk_range_at(offset, &iend).end;
+        let remaining = &self.buffer[end..];
+        if remaining.len() >= CHUNK_HEADER_SIZE {
+            let header = unsafe { &*(remaining.as_ptr() as *const ChunkHeader) };
+            if header.validate_chunk_type().is_ok() {
+                return Err(PngError::InvalidData(
+                    format!("Chunk after IEND chunk. Offset: {end:X} Chunk type: {}", header.get_chunk_type_as_str()),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+    /// Checks that every chunk in the file has one of the `allowed` chunk types. IHDR, IDAT and IEND are
+    /// always allowed (see `ALWAYS_ALLOWED_CHUNK_TYPES`), so they do not need to be listed. Every other
+    /// chunk type, including PLTE, must be in `allowed`. The error reports the first chunk that is not
+    /// allowed, and its index.
+    pub fn validate_allowed_types(&self, allowed: &[&str]) -> Result<(), PngError> {
+        for (i, chunk_info) in self.into_iter().enumerate() {
+            let chunk_type = chunk_info.get_chunk_type();
+            if !ALWAYS_ALLOWED_CHUNK_TYPES.contains(&chunk_type) && !allowed.contains(&chunk_type) {
+                return Err(PngError::InvalidData(
+                    format!("Chunk type is not allowed. Chunk #: {i} Chunk type: {chunk_type}"),
+                ));
+            }
+        }
+
         Ok(())
     }
+    /// Lists each chunk in the file on its own line, with its index, header, crc, whether the crc is
+    /// valid, and the property bits of the chunk type. Meant for debugging malformed files, so the crc is
+    /// checked, but nothing else is validated.
+    pub fn describe(&self) -> String {
+        let mut description = String::new();
+        for (i, chunk) in self.into_iter().enumerate() {
+            let chunk_type = chunk.as_chunk_type();
+            let _ = writeln!(
+                description,
+                "{i:>3}: {:?} {:?} {} {} {} {}{}",
+                chunk.get_header(),
+                chunk.as_chunk_crc(),
+                if chunk.validate_crc() { "OK" } else { "BAD CRC" },
+                if chunk_type.is_ancillary() { "ancillary" } else { "critical" },
+                if chunk_type.is_private() { "private" } else { "public" },
+                if chunk_type.is_safe_to_copy() { "safe-to-copy" } else { "unsafe-to-copy" },
+                if chunk_type.is_reserved() { " reserved" } else { "" },
+            );
+        }
+
+        description
+    }
+    /// Returns the indices of all chunks whose stored crc is exactly 0. Real chunk data essentially
+    /// never hashes to 0, so this is a cheap way to spot encoders that wrote a placeholder crc and
+    /// never filled it in. Only the stored crc is read, nothing is calculated.
+    pub fn zero_crc_chunks(&self) -> Vec<usize> {
+        self.into_iter()
+            .enumerate()
+            .filter(|(_, chunk_info)| chunk_info.get_crc() == 0)
+            .map(|(i, _)| i)
+            .collect()
+    }
+    /// Concatenates the data of every IDAT chunk, in order, into a single buffer. This is the compressed
+    /// image data stream. Returns an empty vec if there are no IDAT chunks.
+    pub fn get_image_data(&self) -> Vec<u8> {
+        let idat_chunks = self.get_chunks_of_type("IDAT");
+        let len = idat_chunks.iter().map(|chunk| chunk.get_length() as usize).sum();
+
+        let mut image_data = Vec::with_capacity(len);
+        for chunk in idat_chunks {
+            image_data.extend_from_slice(chunk.get_chunk_data());
+        }
+
+        image_data
+    }
+    /// Hashes the parts of the file that make up the image: the IHDR chunk data, the PLTE chunk data if
+    /// there is one, and the image data stream. Ancillary chunks are ignored, and so is the way the image
+    /// data is split into IDAT chunks, so two files that only differ in metadata hash the same. The hash is
+    /// 64-bit FNV-1a, so it is the same across platforms and runs.
+    pub fn content_hash(&self) -> u64 {
+        let mut hash = FNV_OFFSET_BASIS;
+        for chunk_type in ["IHDR", "PLTE"] {
+            let data = self.get_chunk_of_type(chunk_type).map(|chunk| chunk.get_chunk_data());
+            // Include the length, so that a missing chunk and an empty chunk hash differently.
+            let len = data.map_or(u64::MAX, |data| data.len() as u64);
+            hash = fnv1a(hash, &len.to_be_bytes());
+            hash = fnv1a(hash, data.unwrap_or_default());
+        }
+        for chunk in self.get_chunks_of_type("IDAT") {
+            hash = fnv1a(hash, chunk.get_chunk_data());
+        }
+
+        hash
+    }
+    /// Returns the offset in the image data stream, as returned by `get_image_data`, where each IDAT
+    /// chunk starts. The first offset is always 0. Returns an empty vec if there are no IDAT chunks.
+    pub fn idat_chunk_boundaries(&self) -> Vec<usize> {
+        self.into_iter()
+            .filter(|i| i.get_chunk_type() == "IDAT")
+            .scan(0, |offset, chunk| {
+                let chunk_offset = *offset;
+                *offset += chunk.get_length() as usize;
+
+                Some(chunk_offset)
+            })
+            .collect()
+    }
+    /// Returns the number of IDAT chunks the image data is split across.
+    pub fn get_image_data_chunk_count(&self) -> usize {
+        self.into_iter()
+            .filter(|i| i.get_chunk_type() == "IDAT")
+            .count()
+    }
+    /// Returns every pair of chunk indices where the two chunks are byte for byte identical, including
+    /// the length, chunk type and crc. Each pair is `(first, second)`, with `first < second`, and the pairs
+    /// are sorted. A chunk that appears three times produces three pairs.
+    pub fn has_redundant_chunks(&self) -> Vec<(usize, usize)> {
+        let mut chunks_by_bytes: BTreeMap<&[u8], Vec<usize>> = BTreeMap::new();
+        for (i, (offset, chunk_info)) in self.chunks_with_offsets().enumerate() {
+            let chunk_len = CHUNK_HEADER_SIZE + chunk_info.get_length() as usize + CHUNK_CRC_SIZE;
+            chunks_by_bytes
+                .entry(&self.buffer[offset..offset + chunk_len])
+                .or_default()
+                .push(i);
+        }
+
+        let mut pairs: Vec<(usize, usize)> = chunks_by_bytes
+            .values()
+            .flat_map(|indices| {
+                indices
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(n, &first)| indices[n + 1..].iter().map(move |&second| (first, second)))
+            })
+            .collect();
+        pairs.sort_unstable();
+
+        pairs
+    }
+    /// Returns true if both files have the same chunks, in the same order. Chunks are compared with
+    /// `ChunkRefs::eq`, so the chunk type, length, chunk data and crc must all match.
+    pub fn chunks_equal(&self, other: &PNGReader) -> bool {
+        self.into_iter().eq(other)
+    }
+    /// Returns the indices of the chunks that differ between the two files, in order. If one file has more
+    /// chunks than the other, every index past the end of the shorter file is included.
+    pub fn diff(&self, other: &PNGReader) -> Vec<usize> {
+        let len = self.chunk_count().max(other.chunk_count());
+        let mut chunks = self.into_iter();
+        let mut other_chunks = other.into_iter();
+
+        (0..len)
+            .filter(|_| chunks.next() != other_chunks.next())
+            .collect()
+    }
+    /// Returns true if the file is an animated PNG, meaning it has an acTL chunk before the first IDAT
+    /// chunk. An acTL chunk after the image data is ignored, like decoders do.
+    pub fn is_apng(&self) -> bool {
+        self.into_iter()
+            .map(|chunk| chunk.get_chunk_type())
+            .take_while(|&chunk_type| chunk_type != "IDAT")
+            .any(|chunk_type| chunk_type == "acTL")
+    }
+    /// Returns how many bytes would be saved by merging all of the IDAT chunks into a single IDAT
+    /// chunk. Each IDAT chunk past the first is a chunk header and crc that would no longer need to
+    /// be written. Returns 0 if the file has one or no IDAT chunks.
+    pub fn idat_coalesce_savings(&self) -> u64 {
+        let idat_chunk_count = self.get_image_data_chunk_count() as u64;
+
+        idat_chunk_count.saturating_sub(1) * (CHUNK_HEADER_SIZE + CHUNK_CRC_SIZE) as u64
+    }
+}
+
+#[cfg(feature = "std")]
+impl PNGReader<'_> {
+    /// Writes the full bytes of each chunk (length, chunk type, chunk data and crc) to its own file in the
+    /// provided directory, creating the directory if needed. Files are named with the chunk index and
+    /// chunk type, like `000_IHDR.chunk`, so that they sort in file order. Returns the paths of the
+    /// written files, in order.
+    pub fn dump_chunks<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>, PngError> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        let mut paths = vec![];
+        for (i, (offset, chunk_info)) in self.chunks_with_offsets().enumerate() {
+            let chunk_len = CHUNK_HEADER_SIZE + chunk_info.get_length() as usize + CHUNK_CRC_SIZE;
+            let path = dir.join(format!("{i:03}_{}.chunk", chunk_info.get_chunk_type()));
 
+            std::fs::write(&path, &self.buffer[offset..offset + chunk_len])?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+    /// Rebuilds the file with every ancillary chunk removed, like tEXt, tIME and eXIf, so that only the
+    /// critical chunks are left. The critical chunks are copied unchanged and in the same order, so
+    /// removing the ancillary chunks is always allowed by the safe-to-copy rules in `ChunkType`.
+    pub fn strip_ancillary(&self) -> Result<Vec<u8>, PngError> {
+        PNGBuilder::new()
+            .with_png(self)
+            .without_chunk_where(|chunk| chunk.is_ancillary())
+            .build()
+    }
 }
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use crate::builder::PNGBuilder;
+    use crate::chunk::header::ChunkHeader;
     use crate::chunk::PNGChunk;
     use crate::chunk::refs::ChunkRefs;
+    use crate::chunk::ty::ancillary::text::TEXt;
+    use crate::chunk::ty::critical::ihdr::IHDRDetails;
+    use crate::consts::CHUNK_HEADER_SIZE;
+    use crate::error::PngError;
     use crate::PNGReader;
 
     #[test]
     fn read_png() {
         let png_file = std::fs::read("ferris.png").expect("Could not read png file");
         let _ = get_refs(&png_file[..]);
-        // for chunk in refs {
-        //     println!("{chunk:?}")
-        // }
+    }
+    #[test]
+    fn describe() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let description = png.describe();
+        let lines: Vec<_> = description.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert!(lines[0].contains("IHDR"));
+        assert!(lines[0].contains("0xBC1F334A } OK critical public unsafe-to-copy"));
+        assert!(lines[1].contains("sRGB"));
+        assert!(lines[1].contains("ancillary"));
+        assert!(lines[8].contains("IEND"));
+    }
+    #[test]
+    fn dimensions() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert_eq!(png.dimensions().unwrap(), (460, 307));
+
+        let no_ihdr = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IDAT", &[]).unwrap())
+            .with_auto_iend(false)
+            .build()
+            .unwrap();
+        let png = unsafe { PNGReader::new_unchecked(&no_ihdr) };
+        let err = png.dimensions().unwrap_err();
+        assert!(matches!(err, PngError::InvalidIhdr(_)));
+        assert!(err.to_string().contains("IHDR"));
+    }
+    #[test]
+    fn color_type_accessors() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        assert_eq!(png.color_type().unwrap(), 6);
+        assert_eq!(png.bit_depth().unwrap(), 8);
+        assert!(!png.is_grayscale().unwrap());
+        assert!(png.has_alpha().unwrap());
+        assert!(!png.uses_palette().unwrap());
+
+        let check = |bit_depth: u8, color_type: u8| {
+            let png_file = PNGBuilder::new()
+                .with_ihdr(1, 1, IHDRDetails::new(bit_depth, color_type, 0, 0, 0).unwrap())
+                .unwrap()
+                .build()
+                .unwrap();
+            let png = PNGReader::new(&png_file[..]).unwrap();
+            (png.is_grayscale().unwrap(), png.has_alpha().unwrap(), png.uses_palette().unwrap())
+        };
+        assert_eq!(check(16, 0), (true, false, false));
+        assert_eq!(check(8, 2), (false, false, false));
+        assert_eq!(check(4, 3), (false, false, true));
+        assert_eq!(check(8, 4), (true, true, false));
+    }
+    #[test]
+    fn validate_required_chunks() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        png.validate_required_chunks().unwrap();
+
+        let palette = IHDRDetails::new(8, 3, 0, 0, 0).unwrap();
+        let check = |chunks: Vec<PNGChunk>| {
+            let png_file = PNGBuilder::new()
+                .with_ihdr(1, 1, palette)
+                .unwrap()
+                .with_chunks(chunks)
+                .build()
+                .expect("Could not build PNG file");
+            PNGReader::new(&png_file[..]).expect("Could not validate PNG.").validate_required_chunks()
+        };
+        let idat = || PNGChunk::new("IDAT", &[]).unwrap();
+        let plte = || PNGChunk::new("PLTE", &[0, 0, 0]).unwrap();
+
+        check(vec![plte(), idat()]).unwrap();
+        let err = check(vec![idat()]).unwrap_err();
+        assert!(err.to_string().contains("Color type 3 requires a PLTE chunk"));
+        let err = check(vec![plte()]).unwrap_err();
+        assert!(err.to_string().contains("at least one IDAT chunk"));
+
+        let no_iend = &png_file[..png_file.len() - 12];
+        let png = unsafe { PNGReader::new_unchecked(no_iend) };
+        let err = png.validate_required_chunks().unwrap_err();
+        assert!(err.to_string().contains("IEND"));
+    }
+    #[test]
+    fn grayscale_with_palette() {
+        for color_type in [0, 4] {
+            let png_file = PNGBuilder::new()
+                .with_ihdr(1, 1, IHDRDetails::new(8, color_type, 0, 0, 0).unwrap())
+                .unwrap()
+                .with_chunk(PNGChunk::new("PLTE", &[0, 0, 0]).unwrap())
+                .with_chunk(PNGChunk::new("IDAT", &[]).unwrap())
+                .build()
+                .expect("Could not build PNG file");
+            let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+            let err = png.validate_required_chunks().unwrap_err();
+            assert!(err.to_string().contains(&format!("grayscale images. Color type: {color_type}")));
+            assert!(png.is_minimal_compliant().is_err());
+        }
+    }
+    #[test]
+    fn validate_iend() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        png.validate_iend().unwrap();
+
+        let aligned = PNGBuilder::new().with_png(&png).build_aligned(4096).unwrap();
+        PNGReader::new(&aligned[..]).unwrap().validate_iend().unwrap();
+
+        let mut non_empty_iend = png_file[..png_file.len() - 12].to_vec();
+        non_empty_iend.extend(PNGChunk::new("IEND", &[0, 1, 2, 3]).unwrap().as_slice());
+        let png = PNGReader::new(&non_empty_iend[..]).expect("Could not validate PNG.");
+        let err = png.validate_iend().unwrap_err();
+        assert!(err.to_string().contains("IEND chunk must be empty"));
+
+        let mut chunk_after_iend = png_file.clone();
+        chunk_after_iend.extend(PNGChunk::new("tEXt", b"Title\0Ferris").unwrap().as_slice());
+        let png = PNGReader::new(&chunk_after_iend[..]).expect("Could not validate PNG.");
+        let err = png.validate_iend().unwrap_err();
+        assert!(err.to_string().contains("Chunk after IEND chunk"));
+        assert!(err.to_string().contains("tEXt"));
+    }
+    #[test]
+    fn chunks_by_property_bits() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("Title", "Ferris")
+            .unwrap()
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        let critical: Vec<&str> = png.critical_chunks().map(|chunk| chunk.get_chunk_type()).collect();
+        assert_eq!(critical, ["IHDR", "IDAT", "IDAT", "IDAT", "IEND"]);
+        let ancillary: Vec<&str> = png.ancillary_chunks().map(|chunk| chunk.get_chunk_type()).collect();
+        assert_eq!(ancillary, ["sRGB", "eXIf", "pHYs", "iTXt", "tEXt"]);
+        let safe_to_copy: Vec<&str> = png.safe_to_copy_chunks().map(|chunk| chunk.get_chunk_type()).collect();
+        assert_eq!(safe_to_copy, ["eXIf", "pHYs", "iTXt", "tEXt"]);
+    }
+    #[test]
+    fn chunk_range() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        assert_eq!(png.chunk_range("IHDR"), Some(8..8 + 25));
+        assert_eq!(png.chunk_range("tIME"), None);
+
+        let idat_ranges = png.chunk_ranges("IDAT");
+        assert_eq!(idat_ranges.len(), 3);
+        assert_eq!(idat_ranges[0].start, 798);
+        assert_eq!(idat_ranges[0].len(), 16384 + 12);
+        assert_eq!(idat_ranges[1].start, idat_ranges[0].end);
+        assert_eq!(png.chunk_range("IDAT"), Some(idat_ranges[0].clone()));
+
+        let iend_range = png.chunk_range("IEND").unwrap();
+        assert_eq!(iend_range.end, png_file.len());
+    }
+    #[test]
+    fn get_header() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        for chunk in &png {
+            assert_eq!(chunk.get_header().get_length(), chunk.get_length());
+            assert_eq!(chunk.get_header().get_chunk_type_as_str(), chunk.get_chunk_type());
+        }
+    }
+    #[test]
+    fn chunks_equal() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let copy = png_file.clone();
+        let png_copy = PNGReader::new(&copy[..]).expect("Could not validate PNG.");
+
+        assert!(png.chunks_equal(&png));
+        assert!(png.chunks_equal(&png_copy));
+        assert!(png.diff(&png_copy).is_empty());
+
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("Title", "Ferris")
+            .unwrap()
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        assert!(!png.chunks_equal(&new_png));
+        assert!(!new_png.chunks_equal(&png));
+        let text_index = (&new_png).into_iter().position(|chunk| chunk.get_chunk_type() == "tEXt").unwrap();
+        // Every chunk from the tEXt chunk on is shifted by one, and the new file has one more chunk.
+        assert_eq!(png.diff(&new_png), (text_index..10).collect::<Vec<_>>());
     }
     fn get_refs(buffer: &[u8]) -> Vec<ChunkRefs>  {
         let png = PNGReader::new(buffer).expect("Could not validate PNG.");
@@ -167,4 +932,973 @@ mod tests {
 
         assert_eq!("teST", info.get_chunk_type())
     }
+
+    #[test]
+    fn chunk_count() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        assert_eq!(png.chunk_count(), 9);
+        assert_eq!(png.chunk_count(), (&png).into_iter().collect::<Vec<_>>().len());
+        assert_eq!(png.get_all_chunk_info().capacity(), 9);
+    }
+
+    #[test]
+    fn chunks_rev() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let mut forward = png.get_all_chunk_info();
+        forward.reverse();
+        let reversed: Vec<ChunkRefs> = png.chunks_rev().collect();
+        assert_eq!(reversed.len(), forward.len());
+        for (a, b) in reversed.iter().zip(&forward) {
+            assert_eq!(a.get_chunk_type(), b.get_chunk_type());
+            assert_eq!(a.get_chunk_data().as_ptr(), b.get_chunk_data().as_ptr());
+        }
+
+        let last_idat = png.chunks_rev().find(|chunk| chunk.get_chunk_type() == "IDAT").unwrap();
+        assert_eq!(last_idat.get_length(), 12672);
+    }
+
+    #[test]
+    fn validate_chunks_error() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let (offset, phys_chunk) = png.chunks_with_offsets().nth(3).unwrap();
+        assert_eq!(phys_chunk.get_chunk_type(), "pHYs");
+        let stored_crc = phys_chunk.get_crc();
+
+        // Zero out the crc, which is the last 4 bytes of the chunk.
+        let crc_offset = offset + CHUNK_HEADER_SIZE + phys_chunk.get_length() as usize;
+        let mut corrupted = png_file.clone();
+        corrupted[crc_offset..crc_offset + 4].copy_from_slice(&0u32.to_be_bytes());
+
+        let err = PNGReader::new(&corrupted).err().unwrap();
+        assert!(matches!(
+            &err,
+            PngError::CrcMismatch { chunk_type, index: 3, offset: o, stored: 0, computed }
+                if chunk_type == "pHYs" && *o == offset && *computed == stored_crc
+        ));
+        let err = err.to_string();
+        assert!(err.contains(&format!("Chunk #: 3 Offset: {offset:X} Chunk type: pHYs")), "{err}");
+        assert!(err.contains(&format!("Chunk crc: 0, Calculated crc: {stored_crc:X}")), "{err}");
+    }
+
+    #[test]
+    fn validate_chunks_every_error() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        // Corrupt the data of the sRGB chunk and the first IDAT chunk
+        let offsets: Vec<usize> = png.chunks_with_offsets().map(|(offset, _)| offset).collect();
+        let mut corrupted = png_file.clone();
+        corrupted[offsets[1] + CHUNK_HEADER_SIZE] ^= 0xFF;
+        corrupted[offsets[5] + CHUNK_HEADER_SIZE] ^= 0xFF;
+        let png = unsafe { PNGReader::new_unchecked(&corrupted) };
+
+        let err = png.validate_chunks().unwrap_err();
+        assert_eq!(err.offset(), Some(offsets[1]));
+        let PngError::CrcMismatches(failures) = err else {
+            panic!("Expected CrcMismatches");
+        };
+        assert_eq!(failures.len(), 2);
+        assert!(matches!(&failures[0], PngError::CrcMismatch { chunk_type, index: 1, .. } if chunk_type == "sRGB"));
+        assert!(matches!(&failures[1], PngError::CrcMismatch { chunk_type, index: 5, .. } if chunk_type == "IDAT"));
+    }
+
+    #[test]
+    fn png_error() {
+        let err = PNGReader::new(b"\x89PNG\r\n\x1A\x0B").err().unwrap();
+        assert!(matches!(err, PngError::BadSignature));
+        let err = PNGReader::new(b"\x89PNG").err().unwrap();
+        assert!(matches!(err, PngError::TruncatedBuffer { offset: 0, expected: 8, got: 4 }));
+
+        assert!(matches!(PNGChunk::new("te5T", &[]), Err(PngError::InvalidChunkType { offset: None, .. })));
+        let err: std::io::Error = PngError::ChunkTooLong { offset: None, len: 0x80000000 }.into();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("Len: 0x80000000"));
+
+        let err: std::io::Error = IHDRDetails::new(3, 6, 0, 0, 0).err().unwrap().into();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn validate_chunks_parallel() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        png.validate_chunks_parallel().unwrap();
+
+        // Corrupt the data of the pHYs chunk
+        let offsets: Vec<usize> = png.chunks_with_offsets().map(|(offset, _)| offset).collect();
+        let mut corrupted = png_file.clone();
+        corrupted[offsets[3] + CHUNK_HEADER_SIZE] ^= 0xFF;
+        let png = unsafe { PNGReader::new_unchecked(&corrupted) };
+
+        let serial = png.validate_chunks().unwrap_err();
+        let parallel = png.validate_chunks_parallel().unwrap_err();
+        assert_eq!(parallel.to_string(), serial.to_string());
+        assert!(matches!(serial, PngError::CrcMismatch { index: 3, .. }));
+        assert!(matches!(parallel, PngError::CrcMismatch { index: 3, .. }));
+
+        // Also corrupt the data of the last IDAT chunk
+        corrupted[offsets[7] + CHUNK_HEADER_SIZE] ^= 0xFF;
+        let png = unsafe { PNGReader::new_unchecked(&corrupted) };
+
+        let serial = png.validate_chunks().unwrap_err();
+        let parallel = png.validate_chunks_parallel().unwrap_err();
+        assert_eq!(parallel.to_string(), serial.to_string());
+        let PngError::CrcMismatches(failures) = parallel else {
+            panic!("Expected CrcMismatches");
+        };
+        assert_eq!(failures.len(), 2);
+        assert!(matches!(failures[0], PngError::CrcMismatch { index: 3, .. }));
+        assert!(matches!(failures[1], PngError::CrcMismatch { index: 7, .. }));
+        assert_eq!(failures[1].offset(), Some(offsets[7]));
+    }
+
+    #[test]
+    fn content_hash() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let hash = png.content_hash();
+
+        let text_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("Comment", "Same image")
+            .unwrap()
+            .without_chunks_of_type("eXIf")
+            .build()
+            .unwrap();
+        let text_png = PNGReader::new(&text_png_file[..]).unwrap();
+        assert_eq!(text_png.content_hash(), hash);
+
+        // Merging the IDAT chunks does not change the image data stream.
+        let merged_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .without_chunks_of_type("IDAT")
+            .with_chunk(PNGChunk::new("IDAT", &png.get_image_data()).unwrap())
+            .build()
+            .unwrap();
+        let merged_png = PNGReader::new(&merged_png_file[..]).unwrap();
+        assert_eq!(merged_png.content_hash(), hash);
+
+        let mut header = png.get_validated_header().unwrap();
+        assert!(header.set_width(461));
+        let resized_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_unique_chunk(header.to_chunk().unwrap())
+            .build()
+            .unwrap();
+        let resized_png = PNGReader::new(&resized_png_file[..]).unwrap();
+        assert_ne!(resized_png.content_hash(), hash);
+    }
+
+    #[test]
+    fn strip_ancillary() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("Title", "Ferris")
+            .unwrap()
+            .with_text("Author", "The crab")
+            .unwrap()
+            .with_chunk(PNGChunk::new("prVt", b"private data").unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        let stripped_file = new_png.strip_ancillary().unwrap();
+        let stripped = PNGReader::new(&stripped_file[..]).expect("Could not validate PNG.");
+        stripped.is_minimal_compliant().unwrap();
+
+        let chunk_types: Vec<&str> = (&stripped).into_iter().map(|chunk| chunk.get_chunk_type()).collect();
+        assert_eq!(chunk_types, ["IHDR", "IDAT", "IDAT", "IDAT", "IEND"]);
+        assert_eq!(stripped.get_image_data(), png.get_image_data());
+    }
+
+    #[test]
+    fn invalid_chunk_type_bytes() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let (offset, _) = png.chunks_with_offsets().find(|(_, chunk)| chunk.get_chunk_type() == "pHYs").unwrap();
+
+        let mut corrupted = png_file.clone();
+        corrupted[offset + 5] = 0xFF;
+
+        let err = PNGReader::new(&corrupted).err().unwrap();
+        assert!(matches!(err, PngError::InvalidChunkType { offset: Some(o), .. } if o == offset));
+        assert!(err.to_string().contains("invalid byte 0xFF. Chunk type bytes: [70, FF, 59, 73]"));
+
+        let png = unsafe { PNGReader::new_unchecked(&corrupted) };
+        assert!(matches!(png.validate_chunks(), Err(PngError::InvalidChunkType { .. })));
+    }
+    #[test]
+    fn error_offsets() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let (offset, _) = png.chunks_with_offsets().find(|(_, chunk)| chunk.get_chunk_type() == "iTXt").unwrap();
+
+        // Corrupt the chunk type
+        let mut corrupted = png_file.clone();
+        corrupted[offset + 4] = b'0';
+        let err = PNGReader::new(&corrupted).err().unwrap();
+        assert!(matches!(err, PngError::InvalidChunkType { .. }));
+        assert_eq!(err.offset(), Some(offset));
+
+        // Corrupt the chunk data
+        let mut corrupted = png_file.clone();
+        corrupted[offset + CHUNK_HEADER_SIZE] ^= 0xFF;
+        let err = PNGReader::new(&corrupted).err().unwrap();
+        assert!(matches!(err, PngError::CrcMismatch { .. }));
+        assert_eq!(err.offset(), Some(offset));
+        assert!(err.to_string().contains(&format!("Offset: {offset:X}")));
+
+        assert_eq!(PNGChunk::new("te5T", &[]).err().unwrap().offset(), None);
+    }
+
+    #[test]
+    fn max_chunk_length() {
+        let mut header = ChunkHeader::new(0, "teST").unwrap();
+        assert!(header.set_length(0x7FFFFFFF));
+        assert!(!header.set_length(0x80000000));
+        assert!(!header.set_length(u32::MAX));
+        assert_eq!(header.get_length(), 0x7FFFFFFF);
+
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let mut bad_length = png_file.clone();
+        bad_length[798..802].copy_from_slice(&0x80000000u32.to_be_bytes());
+        let err = PNGReader::new(&bad_length).err().unwrap();
+        assert!(matches!(err, PngError::ChunkTooLong { offset: Some(798), len: 0x80000000 }));
+        assert_eq!(err.offset(), Some(798));
+        assert!(err.to_string().contains("Offset: 31E"), "{err}");
+
+        let png = unsafe { PNGReader::new_unchecked(&bad_length) };
+        assert_eq!(png.chunk_count(), 5);
+    }
+    #[test]
+    fn truncated_chunks() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+
+        // Cut off in the middle of the first IDAT chunk
+        let png = unsafe { PNGReader::new_unchecked(&png_file[..1000]) };
+        let err = png.validate_chunks().unwrap_err();
+        assert!(matches!(err, PngError::TruncatedBuffer { offset: 798, expected: 16396, got: 202 }));
+        let err: std::io::Error = err.into();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+        // Cut off right after a chunk, before IEND
+        let err = PNGReader::new(&png_file[..798]).err().unwrap();
+        assert!(matches!(err, PngError::TruncatedBuffer { offset: 798, expected: 8, got: 0 }));
+
+        // Length field larger than the rest of the buffer
+        let mut bad_length = png_file.clone();
+        bad_length[798..802].copy_from_slice(&0x7FFFFFFFu32.to_be_bytes());
+        let err = PNGReader::new(&bad_length).err().unwrap();
+        assert!(matches!(err, PngError::TruncatedBuffer { offset: 798, expected: 0x8000000B, .. }));
+
+        // Random truncations and corruptions should never panic.
+        let mut state = 0x2545F4914F6CDD1Du64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+        for _ in 0..200 {
+            let truncated = &png_file[..next() % png_file.len()];
+            assert!(PNGReader::new(truncated).is_err());
+
+            let mut corrupted = png_file.clone();
+            let i = next() % corrupted.len();
+            corrupted[i] = next() as u8;
+            let _ = PNGReader::new(&corrupted);
+        }
+    }
+
+    #[test]
+    fn mutate_chunk_and_set_crc() {
+        // Does not touch the file system, so that it can run under Miri. See .github/workflows/miri.yml.
+        let mut chunk = PNGChunk::new("teST", &[0, 1, 2, 3]).unwrap();
+        chunk.get_chunk_data_mut().copy_from_slice(&[4, 5, 6, 7]);
+        assert!(!chunk.is_valid_crc());
+
+        chunk.calculate_and_set_crc();
+        assert!(chunk.is_valid_crc());
+        assert_eq!(chunk.get_crc(), PNGChunk::compute_crc("teST", &[4, 5, 6, 7]).unwrap());
+
+        chunk.set_chunk_type("seST").unwrap();
+        assert!(chunk.set_length(2));
+        assert_eq!(chunk.get_chunk_data(), [4, 5]);
+    }
+
+    #[test]
+    fn compute_crc() {
+        let chunk = PNGChunk::new("teST", &[0, 1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(PNGChunk::compute_crc("teST", &[0, 1, 2, 3, 4, 5]).unwrap(), chunk.get_crc());
+
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        for chunk_info in &png {
+            let crc = PNGChunk::compute_crc(chunk_info.get_chunk_type(), chunk_info.get_chunk_data()).unwrap();
+            assert_eq!(crc, chunk_info.get_crc());
+        }
+
+        assert!(PNGChunk::compute_crc("te5T", &[]).is_err());
+        assert!(PNGChunk::compute_crc("tEST1", &[]).is_err());
+    }
+
+    #[test]
+    fn iter_within() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        assert_eq!(png.iter_within(0).count(), 0);
+        // IHDR is 25 bytes framed.
+        assert_eq!(png.iter_within(24).count(), 0);
+        assert_eq!(png.iter_within(25).count(), 1);
+        // Everything up to, but not including, the first IDAT.
+        assert_eq!(png.iter_within(798 - 8).count(), 5);
+        assert_eq!(png.iter_within(png_file.len()).count(), png.get_all_chunk_info().len());
+    }
+
+    #[test]
+    fn defer_crcs() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let mut chunk = PNGChunk::new("teST", &[0, 1, 2, 3, 4, 5]).unwrap();
+        chunk.get_chunk_data_mut()[0] = 0xFF;
+
+        let expected_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_chunk(PNGChunk::new("teST", chunk.get_chunk_data()).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let deferred_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_chunk(chunk)
+            .defer_crcs()
+            .build()
+            .expect("Could not build PNG file");
+
+        assert_eq!(expected_png_file, deferred_png_file);
+        PNGReader::new(&deferred_png_file[..]).expect("Could not validate PNG.");
+    }
+
+    #[test]
+    fn get_image_data() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let idat_chunks = png.get_chunks_of_type("IDAT");
+        let image_data = png.get_image_data();
+        assert_eq!(image_data.len(), idat_chunks.iter().map(|c| c.get_length() as usize).sum::<usize>());
+        assert_eq!(&image_data[..16384], idat_chunks[0].get_chunk_data());
+        assert_eq!(&image_data[16384 * 2..], idat_chunks[2].get_chunk_data());
+        assert_eq!(png.get_image_data_chunk_count(), 3);
+        assert_eq!(png.idat_chunk_boundaries(), [0, 16384, 16384 * 2]);
+
+        let png_file = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(png.get_image_data().is_empty());
+        assert_eq!(png.get_image_data_chunk_count(), 0);
+        assert!(png.idat_chunk_boundaries().is_empty());
+    }
+
+    #[test]
+    fn data_as_string_lossy() {
+        let chunk = PNGChunk::new("jsON", br#"{"crab": true}"#).unwrap();
+        assert_eq!(chunk.as_chunk_refs().data_as_string_lossy(), r#"{"crab": true}"#);
+
+        let chunk = PNGChunk::new("teST", b"ok\xFF").unwrap();
+        assert_eq!(chunk.as_chunk_refs().data_as_string_lossy(), "ok\u{FFFD}");
+    }
+
+    #[test]
+    fn build_aligned() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let aligned_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .build_aligned(256)
+            .expect("Could not build PNG file");
+
+        assert_eq!(aligned_png_file.len() % 256, 0);
+        assert_eq!(&aligned_png_file[..png_file.len()], &png_file[..]);
+        assert!(aligned_png_file[png_file.len()..].iter().all(|&b| b == 0));
+
+        let aligned_png = PNGReader::new(&aligned_png_file[..]).expect("Could not validate PNG.");
+        assert_eq!(aligned_png.get_all_chunk_info().len(), png.get_all_chunk_info().len());
+        aligned_png.is_minimal_compliant().unwrap();
+
+        assert!(PNGBuilder::new().with_png(&png).build_aligned(0).is_err());
+    }
+
+    #[test]
+    fn has_redundant_chunks() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(png.has_redundant_chunks().is_empty());
+
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("Comment", "crab").unwrap()
+            .with_text("Comment", "crab").unwrap()
+            .with_text("Comment", "not a crab").unwrap()
+            .with_text("Comment", "crab").unwrap()
+            .with_png(&png)
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+        let pairs = new_png.has_redundant_chunks();
+
+        // The three matching comments at 8, 9 and 11
+        assert!(pairs.contains(&(8, 9)));
+        assert!(pairs.contains(&(8, 11)));
+        assert!(pairs.contains(&(9, 11)));
+        assert!(!pairs.iter().any(|&(first, second)| first == 10 || second == 10));
+        // Every chunk of ferris.png appears twice, except IEND
+        assert!(pairs.contains(&(0, 12)));
+        assert_eq!(pairs.len(), 3 + 8);
+    }
+
+    #[test]
+    fn without_chunks() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("Title", "Ferris").unwrap()
+            .with_chunk(PNGChunk::new("prIV", &[1, 2, 3]).unwrap())
+            .with_text("Author", "Rust").unwrap()
+            .without_chunks_of_type("tEXt")
+            .with_chunk(PNGChunk::new("prIV", &[]).unwrap())
+            .without_chunk_where(|chunk| chunk.get_chunk_type() == "prIV" && !chunk.get_chunk_data().is_empty())
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        assert!(new_png.get_chunk_of_type("tEXt").is_none());
+        let private_chunks = new_png.get_chunks_of_type("prIV");
+        assert_eq!(private_chunks.len(), 1);
+        assert!(private_chunks[0].get_chunk_data().is_empty());
+        assert_eq!(new_png.get_chunks_of_type("IDAT").len(), 3);
+    }
+
+    #[test]
+    fn palette_size() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert_eq!(png.palette_size(), None);
+
+        let build = |palette: &[u8]| {
+            PNGBuilder::new()
+                .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 3, 0, 0, 0]).unwrap())
+                .with_chunk(PNGChunk::new("PLTE", palette).unwrap())
+                .build()
+                .expect("Could not build PNG file")
+        };
+
+        let png_file = build(&[0, 0, 0, 255, 255, 255]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert_eq!(png.palette_size(), Some(2));
+
+        let png_file = build(&[0, 0, 0, 255]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert_eq!(png.palette_size(), None);
+    }
+
+    #[test]
+    fn with_unique_chunk() {
+        let first_header = [0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0];
+        let last_header = [0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0];
+
+        let png_file = PNGBuilder::new()
+            .with_unique_chunk(PNGChunk::new("IHDR", &first_header).unwrap())
+            .with_chunk(PNGChunk::new("gAMA", &[0, 0, 177, 143]).unwrap())
+            .with_chunk(PNGChunk::new("gAMA", &[0, 0, 177, 143]).unwrap())
+            .with_unique_chunk(PNGChunk::new("IHDR", &last_header).unwrap())
+            .with_unique_chunk(PNGChunk::new("gAMA", &[0, 1, 134, 160]).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let chunks = png.get_all_chunk_info();
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].get_chunk_type(), "IHDR");
+        assert_eq!(chunks[0].get_chunk_data(), last_header);
+        assert_eq!(chunks[1].get_chunk_type(), "gAMA");
+        assert_eq!(chunks[1].get_chunk_data(), [0, 1, 134, 160]);
+    }
+
+    #[test]
+    fn retain_chunks() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let mut visited = 0;
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("xmp:Creator", "Ferris").unwrap()
+            .with_text("Title", "Ferris").unwrap()
+            .with_text("xmp:Tool", "Rust").unwrap()
+            .retain_chunks(|chunk| {
+                visited += 1;
+                chunk.get_chunk_type() != "tEXt" || !chunk.get_chunk_data().starts_with(b"xmp")
+            })
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        assert_eq!(visited, 11);
+        let chunk_types: Vec<_> = new_png.get_all_chunk_info().iter().map(|chunk| chunk.get_chunk_type()).collect();
+        assert_eq!(chunk_types, ["IHDR", "sRGB", "eXIf", "pHYs", "iTXt", "IDAT", "IDAT", "IDAT", "tEXt", "IEND"]);
+        assert!(new_png.get_chunk_of_type("tEXt").unwrap().get_chunk_data().starts_with(b"Title"));
+    }
+
+    #[test]
+    fn build_empty() {
+        let err = PNGBuilder::new().build().unwrap_err();
+        assert!(matches!(err, PngError::InvalidData(_)));
+
+        let only_iend = PNGBuilder::new().with_chunk(PNGChunk::new("IEND", &[]).unwrap());
+        assert!(only_iend.build().is_err());
+    }
+
+    #[test]
+    fn validate_allowed_types() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        png.validate_allowed_types(&["sRGB", "eXIf", "pHYs", "iTXt"]).unwrap();
+
+        let err = png.validate_allowed_types(&["sRGB", "pHYs", "iTXt"]).unwrap_err();
+        assert!(matches!(err, PngError::InvalidData(_)));
+        assert!(err.to_string().contains("Chunk #: 2 Chunk type: eXIf"));
+
+        assert!(png.validate_allowed_types(&[]).is_err());
+    }
+
+    #[test]
+    fn write_to() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let mut written = vec![];
+        PNGBuilder::new().with_png(&png).write_to(&mut written).unwrap();
+        let written_png = PNGReader::new(&written[..]).expect("Could not validate PNG.");
+        written_png.validate_chunks().unwrap();
+        assert_eq!(written, png_file);
+
+        let mut chunk = PNGChunk::new("teST", &[0, 1, 2, 3, 4, 5]).unwrap();
+        chunk.get_chunk_data_mut()[0] = 0xFF;
+        let mut deferred = vec![];
+        PNGBuilder::new()
+            .with_png(&png)
+            .with_chunk(chunk)
+            .defer_crcs()
+            .write_to(&mut deferred)
+            .unwrap();
+        PNGReader::new(&deferred[..]).unwrap().validate_chunks().unwrap();
+
+        assert!(PNGBuilder::new().write_to(&mut vec![]).is_err());
+    }
+
+    #[test]
+    fn without_auto_iend() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let new_png_file = PNGBuilder::new().with_png(&png).with_auto_iend(false).build().unwrap();
+        assert_eq!(new_png_file, png_file[..png_file.len() - 12]);
+        let new_png = unsafe { PNGReader::new_unchecked(&new_png_file[..]) };
+        assert!(new_png.get_chunk_of_type("IEND").is_none());
+        assert!(matches!(PNGReader::new(&new_png_file[..]), Err(PngError::TruncatedBuffer { .. })));
+
+        let mut written = vec![];
+        PNGBuilder::new().with_png(&png).with_auto_iend(false).write_to(&mut written).unwrap();
+        assert_eq!(written, new_png_file);
+
+        // A fragment without an IHDR chunk is allowed, but an IHDR chunk still has to be first.
+        let fragment = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IDAT", &[]).unwrap())
+            .with_auto_iend(false)
+            .build();
+        assert!(fragment.is_ok());
+        let fragment = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IDAT", &[]).unwrap())
+            .with_png(&png)
+            .with_auto_iend(false)
+            .build();
+        assert!(fragment.is_err());
+    }
+
+    #[test]
+    fn duplicate_single_instance_chunks() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let ihdr = png.get_chunk_of_type("IHDR").unwrap();
+
+        let err = PNGBuilder::new().with_png(&png).with_chunk(ihdr).try_build().unwrap_err();
+        assert!(matches!(&err, PngError::InvalidData(_)));
+        assert!(err.to_string().contains("Duplicate IHDR chunk. Chunk #: 8"), "{err}");
+        // `build` does not check for duplicates.
+        assert!(PNGBuilder::new().with_png(&png).with_chunk(ihdr).build().is_ok());
+        assert_eq!(PNGBuilder::new().with_png(&png).try_build().unwrap(), png_file);
+
+        let plte = PNGChunk::new("PLTE", &[0, 0, 0]).unwrap();
+        let err = PNGBuilder::new()
+            .with_png(&png)
+            .with_chunk(plte.clone())
+            .with_chunk(plte)
+            .try_build()
+            .unwrap_err();
+        assert!(matches!(&err, PngError::InvalidData(_)));
+        assert!(err.to_string().contains("Duplicate PLTE chunk. Chunk #: 9"), "{err}");
+
+        // IEND chunks are skipped when they are added, so an extra IEND is not a duplicate.
+        let iend = png.get_chunk_of_type("IEND").unwrap();
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_chunk(iend)
+            .with_text("Title", "Ferris")
+            .unwrap()
+            .try_build()
+            .unwrap();
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+        assert_eq!(new_png.get_all_chunk_info().last().unwrap().get_chunk_type(), "IEND");
+        assert_eq!(new_png.chunk_count(), 10);
+    }
+
+    #[test]
+    fn encoded_len() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let builder = PNGBuilder::new().with_png(&png).with_text("Title", "Ferris").unwrap();
+        let encoded_len = builder.encoded_len();
+        assert_eq!(encoded_len, png_file.len() + 12 + 12);
+        assert_eq!(builder.build().unwrap().len(), encoded_len);
+
+        let builder = PNGBuilder::new().with_png(&png).with_auto_iend(false);
+        let encoded_len = builder.encoded_len();
+        assert_eq!(builder.build().unwrap().len(), encoded_len);
+
+        assert_eq!(PNGBuilder::new().encoded_len(), 8 + 12);
+    }
+
+    #[test]
+    fn with_raw_chunk() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let chunk = PNGChunk::new("tEXt", b"Title\0Ferris").unwrap();
+
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_raw_chunk(chunk.as_slice().to_vec())
+            .unwrap()
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+        let text = new_png.get_chunk_of_type("tEXt").unwrap();
+        assert!(text == chunk.as_chunk_refs());
+
+        let mut bad_crc = chunk.as_slice().to_vec();
+        *bad_crc.last_mut().unwrap() ^= 0xFF;
+        assert!(PNGBuilder::new().with_raw_chunk(bad_crc).is_err());
+
+        let truncated = chunk.as_slice()[..chunk.as_slice().len() - 1].to_vec();
+        assert!(PNGBuilder::new().with_raw_chunk(truncated).is_err());
+    }
+
+    #[test]
+    fn with_chunks_into_iter() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let from_array = PNGBuilder::new()
+            .with_chunks([
+                PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]).unwrap(),
+                PNGChunk::new("IDAT", &[]).unwrap(),
+            ])
+            .build()
+            .expect("Could not build PNG file");
+        let from_array = PNGReader::new(&from_array[..]).expect("Could not validate PNG.");
+        assert_eq!(from_array.chunk_count(), 3);
+
+        let from_iter = PNGBuilder::new()
+            .with_chunks(png.into_iter().filter(|chunk| chunk.get_chunk_type() != "eXIf"))
+            .build()
+            .expect("Could not build PNG file");
+        let from_iter = PNGReader::new(&from_iter[..]).expect("Could not validate PNG.");
+        assert_eq!(from_iter.chunk_count(), 8);
+        assert!(from_iter.exif().is_none());
+    }
+
+    #[test]
+    fn collect_builder() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let builder: PNGBuilder = (&png)
+            .into_iter()
+            .filter(|chunk| chunk.get_chunk_type() != "iTXt")
+            .map(PNGChunk::from)
+            .collect();
+        let new_png_file = builder.build().expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+        new_png.is_minimal_compliant().unwrap();
+        assert_eq!(new_png.chunk_count(), 8);
+        assert!(new_png.get_chunk_of_type("iTXt").is_none());
+
+        let borrowed: PNGBuilder = png.into_iter().collect();
+        assert_eq!(borrowed.build().unwrap(), png_file);
+    }
+
+    #[test]
+    fn builder_default() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let new_png_file = PNGBuilder::new().with_png(&png).build().unwrap();
+        let default_png_file = PNGBuilder::default().with_png(&png).build().unwrap();
+        assert_eq!(default_png_file, new_png_file);
+    }
+
+    #[test]
+    fn with_ihdr() {
+        let details = IHDRDetails::new(8, 6, 0, 0, 0).unwrap();
+        let png_file = PNGBuilder::new()
+            .with_ihdr(2, 3, details)
+            .unwrap()
+            .with_chunk(PNGChunk::new("IDAT", &[]).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let header = png.get_validated_header().unwrap();
+        assert_eq!(header.get_width(), 2);
+        assert_eq!(header.get_height(), 3);
+        assert_eq!(header.get_details().get_color_type(), 6);
+        assert_eq!(png.get_all_chunk_info().len(), 3);
+
+        assert!(PNGBuilder::new().with_ihdr(0, 3, details).is_err());
+        assert!(PNGBuilder::new().with_ihdr(2, -1, details).is_err());
+    }
+
+    #[test]
+    fn build_ordered() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let ordered_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .build_ordered()
+            .expect("Could not build PNG file");
+        assert_eq!(ordered_png_file, png_file);
+
+        let ihdr = PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 3, 0, 0, 0]).unwrap();
+        let err = PNGBuilder::new()
+            .with_chunk(ihdr)
+            .with_chunk(PNGChunk::new("IDAT", &[]).unwrap())
+            .with_chunk(PNGChunk::new("PLTE", &[0, 0, 0]).unwrap())
+            .build_ordered()
+            .unwrap_err();
+        assert!(err.to_string().contains("PLTE must come before IDAT"));
+    }
+
+    #[test]
+    fn chunk_refs_from_raw() {
+        let chunk = PNGChunk::new("teST", &[0, 1, 2, 3, 4, 5]).unwrap();
+        let refs = ChunkRefs::from_raw(chunk.as_slice()).unwrap();
+
+        assert_eq!(refs.get_chunk_type(), "teST");
+        assert_eq!(refs.get_chunk_data(), [0, 1, 2, 3, 4, 5]);
+        assert!(refs.validate_crc());
+
+        let dir = std::env::temp_dir().join("png-util-chunk-refs-from-raw");
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let paths = png.dump_chunks(&dir).unwrap();
+        let dumped_chunk = std::fs::read(&paths[3]).unwrap();
+        let refs = ChunkRefs::from_raw(&dumped_chunk).unwrap();
+        assert_eq!(refs.get_chunk_type(), "pHYs");
+        assert!(refs.validate_crc());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let slice = chunk.as_slice();
+        assert!(ChunkRefs::from_raw(&slice[..7]).is_err());
+        assert!(ChunkRefs::from_raw(&slice[..slice.len() - 1]).is_err());
+        let mut extra = slice.to_vec();
+        extra.push(0);
+        assert!(ChunkRefs::from_raw(&extra).is_err());
+
+        let mut too_long = slice.to_vec();
+        too_long[..4].copy_from_slice(&0x80000000u32.to_be_bytes());
+        assert!(matches!(ChunkRefs::from_raw(&too_long), Err(PngError::ChunkTooLong { offset: None, len: 0x80000000 })));
+    }
+
+    #[test]
+    fn zero_crc_chunks() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(png.zero_crc_chunks().is_empty());
+
+        // Zero out the crc of the IHDR chunk, which sits right after the 13 bytes of IHDR data.
+        let mut broken_file = png_file.clone();
+        broken_file[29..33].copy_from_slice(&[0; 4]);
+        let broken_png = unsafe { PNGReader::new_unchecked(&broken_file[..]) };
+
+        assert_eq!(broken_png.zero_crc_chunks(), vec![0]);
+    }
+
+    #[test]
+    fn validated_chunk() {
+        let bad_header = PNGChunk::new("IHDR", &[0, 0, 0, 0, 0, 0, 0, 1, 8, 6, 0, 0, 0]).unwrap();
+        assert!(PNGBuilder::new().with_validated_chunk(bad_header).is_err());
+
+        let short_header = PNGChunk::new("IHDR", &[0, 0, 0, 1]).unwrap();
+        assert!(PNGBuilder::new().with_validated_chunk(short_header).is_err());
+
+        let bad_keyword = PNGChunk::new("tEXt", b"Title \0Ferris").unwrap();
+        assert!(matches!(PNGBuilder::new().with_validated_chunk(bad_keyword), Err(PngError::InvalidData(_))));
+        let no_separator = PNGChunk::new("tEXt", b"Title").unwrap();
+        assert!(PNGBuilder::new().with_validated_chunk(no_separator).is_err());
+        let text = TEXt::new("Title", "Ferris").unwrap();
+        assert!(PNGBuilder::new().with_validated_chunk(text).is_ok());
+
+        let bad_chromaticity = PNGChunk::new("cHRM", &[0xFF; 32]).unwrap();
+        assert!(matches!(PNGBuilder::new().with_validated_chunk(bad_chromaticity), Err(PngError::InvalidData(_))));
+        let bad_palette = PNGChunk::new("sPLT", b"Palette\0\x04").unwrap();
+        assert!(matches!(PNGBuilder::new().with_validated_chunk(bad_palette), Err(PngError::InvalidData(_))));
+        let bad_exif = PNGChunk::new("eXIf", b"Exif\0\0").unwrap();
+        assert!(matches!(PNGBuilder::new().with_validated_chunk(bad_exif), Err(PngError::InvalidData(_))));
+        let exif = PNGChunk::new("eXIf", b"II*\0\x08\0\0\0").unwrap();
+        assert!(PNGBuilder::new().with_validated_chunk(exif).is_ok());
+
+        let header = PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]).unwrap();
+        let unknown = PNGChunk::new("teST", &[0, 1, 2, 3, 4, 5]).unwrap();
+        let new_png_file = PNGBuilder::new()
+            .with_validated_chunk(header)
+            .and_then(|builder| builder.with_validated_chunk(unknown))
+            .expect("Could not add validated chunks")
+            .build()
+            .expect("Could not build PNG file");
+
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+        assert!(new_png.get_chunk_of_type("teST").is_some())
+    }
+
+    #[test]
+    fn idat_coalesce_savings() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        // ferris.png has 3 IDAT chunks.
+        assert_eq!(png.idat_coalesce_savings(), 24);
+    }
+
+    #[test]
+    fn borrowed_chunks() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        // Borrowed chunks are written as they are in the source file, so rebuilding a file that only
+        // uses borrowed chunks should give back the same bytes.
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .build()
+            .expect("Could not build PNG file");
+
+        assert_eq!(new_png_file, png_file);
+    }
+
+    #[test]
+    fn bytes_between() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let pre_image = png.bytes_between("IHDR", "IDAT").unwrap();
+        assert_eq!(pre_image, &png_file[8..798]);
+
+        assert!(png.bytes_between("IDAT", "IHDR").is_none());
+        assert!(png.bytes_between("IHDR", "teST").is_none());
+        assert!(png.bytes_between("teST", "IDAT").is_none());
+    }
+
+    #[test]
+    fn with_text() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        assert!(PNGBuilder::new().with_text("Comment ", "Trailing space").is_err());
+
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("Comment", "Hello, Ferris")
+            .expect("Could not add tEXt chunk")
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        let text = new_png.get_chunk_of_type("tEXt").unwrap();
+        assert_eq!(text.get_chunk_data(), b"Comment\0Hello, Ferris");
+    }
+
+    #[test]
+    fn dump_chunks() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let dir = std::env::temp_dir().join(format!("png-util-dump-chunks-{}", std::process::id()));
+
+        let paths = png.dump_chunks(&dir).expect("Could not dump chunks");
+
+        let file_names: Vec<_> = paths
+            .iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(file_names[0], "000_IHDR.chunk");
+        assert_eq!(file_names[5], "005_IDAT.chunk");
+        assert_eq!(file_names[8], "008_IEND.chunk");
+
+        let header = std::fs::read(&paths[0]).unwrap();
+        assert_eq!(header, &png_file[8..33]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn is_minimal_compliant() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        png.is_minimal_compliant().unwrap();
+
+        let ihdr = |color_type: u8| PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, color_type, 0, 0, 0]).unwrap();
+        let idat = || PNGChunk::new("IDAT", &[]).unwrap();
+        let plte = || PNGChunk::new("PLTE", &[0, 0, 0]).unwrap();
+        let check = |chunks: Vec<PNGChunk>| {
+            let png_file = PNGBuilder::new().with_chunks(chunks).build().expect("Could not build PNG file");
+            PNGReader::new(&png_file[..]).expect("Could not validate PNG.").is_minimal_compliant()
+        };
+
+        assert!(check(vec![ihdr(3), plte(), idat()]).is_ok());
+        assert!(check(vec![ihdr(2), plte(), idat()]).is_ok());
+        assert!(check(vec![ihdr(3), idat()]).is_err());
+        assert!(check(vec![ihdr(0), plte(), idat()]).is_err());
+        assert!(check(vec![ihdr(6)]).is_err());
+        assert!(check(vec![ihdr(6), idat(), ihdr(6)]).is_err());
+
+        // IEND missing
+        let png_file = &png_file[..png_file.len() - 12];
+        assert!(PNGReader::new(png_file).is_err());
+        let png = unsafe { PNGReader::new_unchecked(png_file) };
+        assert!(png.is_minimal_compliant().is_err());
+    }
 }
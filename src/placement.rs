@@ -0,0 +1,163 @@
+use crate::chunk::ty::ancillary::offs::{OFFS, UNIT_MICROMETER};
+use crate::chunk::ty::ancillary::phys::{PHYS, UNIT_METER};
+use crate::PNGReader;
+
+/// The physical position and size of an image, in meters. Built from the oFFs chunk (position) and
+/// the pHYs chunk (scale).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Placement {
+    /// Position of the left edge of the image on the X axis, in meters.
+    pub x: f64,
+    /// Position of the top edge of the image on the Y axis, in meters.
+    pub y: f64,
+    /// Width of the image, in meters.
+    pub width: f64,
+    /// Height of the image, in meters.
+    pub height: f64,
+    /// True if there was no oFFs chunk, so the position defaulted to the origin.
+    pub offset_defaulted: bool,
+    /// True if there was no pHYs chunk, or the pHYs chunk only defined the pixel aspect ratio, so the
+    /// scale defaulted to `DEFAULT_PIXELS_PER_METER`.
+    pub scale_defaulted: bool,
+}
+
+/// The scale used when the file does not specify one. 72 DPI, rounded to pixels per meter.
+pub const DEFAULT_PIXELS_PER_METER: f64 = 2835.0;
+const MICROMETERS_PER_METER: f64 = 1_000_000.0;
+
+impl PNGReader<'_> {
+    /// Combines the oFFs and pHYs chunks into the physical position and size of the image, in meters.
+    /// Returns `None` if neither chunk is present, or if the file does not have a valid IHDR chunk.
+    ///
+    /// When there is no oFFs chunk, the position defaults to the origin. When there is no pHYs chunk,
+    /// the scale defaults to `DEFAULT_PIXELS_PER_METER` on both axes. A pHYs chunk with an unknown unit
+    /// only defines the pixel aspect ratio, so the default scale is used for the X axis and the Y axis is
+    /// scaled to keep the aspect ratio. The `Placement` notes which of the two were defaulted. Malformed
+    /// oFFs or pHYs chunks are treated as missing.
+    pub fn physical_placement(&self) -> Option<Placement> {
+        let offs_chunk = self.get_chunk_of_type("oFFs");
+        let phys_chunk = self.get_chunk_of_type("pHYs");
+        if offs_chunk.is_none() && phys_chunk.is_none() {
+            return None;
+        }
+
+        let header = self.get_validated_header().ok()?;
+
+        let physical = phys_chunk
+            .as_ref()
+            .and_then(PHYS::from_chunk_refs)
+            .filter(|physical| physical.validate().is_ok() && physical.get_x_ppu() != 0 && physical.get_y_ppu() != 0);
+        let (x_ppm, y_ppm, scale_defaulted) = match physical {
+            Some(physical) if physical.get_unit() == UNIT_METER => {
+                (physical.get_x_ppu() as f64, physical.get_y_ppu() as f64, false)
+            }
+            Some(physical) => {
+                let aspect = physical.get_y_ppu() as f64 / physical.get_x_ppu() as f64;
+                (DEFAULT_PIXELS_PER_METER, DEFAULT_PIXELS_PER_METER * aspect, true)
+            }
+            None => (DEFAULT_PIXELS_PER_METER, DEFAULT_PIXELS_PER_METER, true),
+        };
+
+        let offset = offs_chunk
+            .as_ref()
+            .and_then(OFFS::from_chunk_refs)
+            .filter(|offset| offset.validate().is_ok());
+        let (x, y) = match offset {
+            Some(offset) if offset.get_unit() == UNIT_MICROMETER => (
+                offset.get_x_position() as f64 / MICROMETERS_PER_METER,
+                offset.get_y_position() as f64 / MICROMETERS_PER_METER,
+            ),
+            Some(offset) => (
+                offset.get_x_position() as f64 / x_ppm,
+                offset.get_y_position() as f64 / y_ppm,
+            ),
+            None => (0.0, 0.0),
+        };
+
+        Some(Placement {
+            x,
+            y,
+            width: header.get_width() as f64 / x_ppm,
+            height: header.get_height() as f64 / y_ppm,
+            offset_defaulted: offset.is_none(),
+            scale_defaulted,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::builder::PNGBuilder;
+    use crate::chunk::ty::ancillary::offs::{OFFS, UNIT_MICROMETER, UNIT_PIXEL};
+    use crate::chunk::ty::ancillary::phys::{PHYS, UNIT_METER};
+    use crate::chunk::PNGChunk;
+    use crate::placement::DEFAULT_PIXELS_PER_METER;
+    use crate::PNGReader;
+
+    fn build(chunks: Vec<PNGChunk>) -> Vec<u8> {
+        PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 100, 0, 0, 0, 50, 8, 6, 0, 0, 0]).unwrap())
+            .with_chunks(chunks)
+            .build()
+            .expect("Could not build PNG file")
+    }
+
+    #[test]
+    fn physical_placement() {
+        let png_file = build(vec![
+            OFFS::new(10_000, -20_000, UNIT_MICROMETER).unwrap(),
+            PHYS::new(1000, 500, UNIT_METER).unwrap(),
+        ]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let placement = png.physical_placement().unwrap();
+
+        assert_eq!(placement.x, 0.01);
+        assert_eq!(placement.y, -0.02);
+        assert_eq!(placement.width, 0.1);
+        assert_eq!(placement.height, 0.1);
+        assert!(!placement.offset_defaulted);
+        assert!(!placement.scale_defaulted);
+
+        let png_file = build(vec![
+            OFFS::new(500, 250, UNIT_PIXEL).unwrap(),
+            PHYS::new(1000, 500, UNIT_METER).unwrap(),
+        ]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let placement = png.physical_placement().unwrap();
+
+        assert_eq!(placement.x, 0.5);
+        assert_eq!(placement.y, 0.5);
+    }
+
+    #[test]
+    fn defaulted_placement() {
+        let png_file = build(vec![]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        assert!(png.physical_placement().is_none());
+
+        let png_file = build(vec![PHYS::new(1000, 1000, UNIT_METER).unwrap()]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let placement = png.physical_placement().unwrap();
+
+        assert_eq!((placement.x, placement.y), (0.0, 0.0));
+        assert!(placement.offset_defaulted);
+        assert!(!placement.scale_defaulted);
+
+        let png_file = build(vec![OFFS::new(0, 0, UNIT_MICROMETER).unwrap()]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let placement = png.physical_placement().unwrap();
+
+        assert_eq!(placement.width, 100.0 / DEFAULT_PIXELS_PER_METER);
+        assert!(!placement.offset_defaulted);
+        assert!(placement.scale_defaulted);
+
+        // Unknown unit, so only the aspect ratio is kept.
+        let png_file = build(vec![PHYS::new(1, 2, 0).unwrap()]);
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let placement = png.physical_placement().unwrap();
+
+        assert_eq!(placement.width, 100.0 / DEFAULT_PIXELS_PER_METER);
+        assert_eq!(placement.height, 50.0 / (DEFAULT_PIXELS_PER_METER * 2.0));
+        assert!(placement.scale_defaulted);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::chunk::crc;
+use crate::chunk::header::ChunkHeader;
+use crate::consts::{CHUNK_CRC_SIZE, CHUNK_HEADER_SIZE, PNG_SIGNATURE_LENGTH};
+use crate::error::PngError;
+use crate::PNGReader;
+
+/// Like `PNGReader`, but over a mutable buffer, so that chunks can be edited in place without rebuilding
+/// the file. Chunk lengths can not be changed, since that would move every chunk after it.
+pub struct PNGReaderMut<'a> {
+    buffer: &'a mut [u8],
+}
+
+impl<'a> PNGReaderMut<'a> {
+    /// Validates the buffer the same way as `PNGReader::new`, and then wraps it.
+    pub fn new(buffer: &'a mut [u8]) -> Result<Self, PngError> {
+        PNGReader::new(buffer)?;
+
+        Ok(PNGReaderMut { buffer })
+    }
+    /// Gets a `PNGReader` over the buffer, to read the chunks without editing them.
+    pub fn as_reader(&self) -> PNGReader<'_> {
+        // Safety: The buffer was validated in `new`, and every `ChunkMut` rewrites the crc when it is
+        // done editing the chunk, so the buffer is still valid.
+        unsafe { PNGReader::new_unchecked(self.buffer) }
+    }
+    /// Iterates over the chunks in the file, up to and including the IEND chunk. Each chunk borrows its
+    /// own part of the buffer, so chunks can be held and edited at the same time.
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut {
+            buffer: &mut self.buffer[PNG_SIGNATURE_LENGTH..],
+            done: false,
+        }
+    }
+}
+
+pub struct IterMut<'a> {
+    buffer: &'a mut [u8],
+    done: bool,
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = ChunkMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.buffer.len() < CHUNK_HEADER_SIZE + CHUNK_CRC_SIZE {
+            return None;
+        }
+
+        let length = u32::from_be_bytes(self.buffer[..4].try_into().ok()?) as usize;
+        let chunk_len = CHUNK_HEADER_SIZE + length + CHUNK_CRC_SIZE;
+        if chunk_len > self.buffer.len() {
+            return None;
+        }
+
+        // Split the chunk off of the front of the buffer, so that the chunk and the rest of the buffer
+        // don't overlap.
+        let buffer = core::mem::take(&mut self.buffer);
+        let (chunk, rest) = buffer.split_at_mut(chunk_len);
+        self.buffer = rest;
+
+        let chunk = ChunkMut { chunk, dirty: false };
+        self.done = chunk.get_chunk_type() == "IEND";
+
+        Some(chunk)
+    }
+}
+
+/// A mutable view of a single chunk in a `PNGReaderMut`. The crc is recalculated and written when the
+/// `ChunkMut` is dropped, or when `commit` is called. Dropping a chunk that was never borrowed mutably
+/// does not recalculate the crc.
+pub struct ChunkMut<'a> {
+    chunk: &'a mut [u8],
+    /// Set when the chunk type or chunk data may have changed since the crc was last written.
+    dirty: bool,
+}
+
+impl ChunkMut<'_> {
+    #[inline(always)]
+    pub fn get_length(&self) -> u32 {
+        self.as_chunk_header().get_length()
+    }
+    #[inline(always)]
+    pub fn get_chunk_type(&self) -> &str {
+        self.as_chunk_header().get_chunk_type_as_str()
+    }
+    /// Sets the chunk type, like flipping the safe-to-copy bit. Fails if the chunk type is invalid.
+    pub fn set_chunk_type(&mut self, chunk_type: &str) -> Result<(), PngError> {
+        self.as_chunk_header_mut().set_chunk_type(chunk_type)?;
+        self.dirty = true;
+
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn get_chunk_data(&self) -> &[u8] {
+        let data_end = self.chunk.len() - CHUNK_CRC_SIZE;
+        &self.chunk[CHUNK_HEADER_SIZE..data_end]
+    }
+    #[inline(always)]
+    pub fn get_chunk_data_mut(&mut self) -> &mut [u8] {
+        self.dirty = true;
+        let data_end = self.chunk.len() - CHUNK_CRC_SIZE;
+        &mut self.chunk[CHUNK_HEADER_SIZE..data_end]
+    }
+    #[inline(always)]
+    pub fn get_crc(&self) -> u32 {
+        let crc_start = self.chunk.len() - CHUNK_CRC_SIZE;
+        u32::from_be_bytes(self.chunk[crc_start..].try_into().unwrap())
+    }
+    /// Recalculates the crc from the chunk type and chunk data, and writes it to the buffer.
+    pub fn commit(&mut self) {
+        let crc_start = self.chunk.len() - CHUNK_CRC_SIZE;
+        // The crc covers the chunk type and chunk data, but not the length field.
+        let crc = crc::crc(&self.chunk[core::mem::size_of::<u32>()..crc_start]);
+        self.chunk[crc_start..].copy_from_slice(&crc.to_be_bytes());
+        self.dirty = false;
+    }
+    #[inline(always)]
+    fn as_chunk_header(&self) -> &ChunkHeader {
+        unsafe { &*(self.chunk.as_ptr() as *const ChunkHeader) }
+    }
+    #[inline(always)]
+    fn as_chunk_header_mut(&mut self) -> &mut ChunkHeader {
+        unsafe { &mut *(self.chunk.as_mut_ptr() as *mut ChunkHeader) }
+    }
+}
+
+impl Drop for ChunkMut<'_> {
+    fn drop(&mut self) {
+        if self.dirty {
+            self.commit();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::builder::PNGBuilder;
+    use crate::chunk::PNGChunk;
+    use crate::reader_mut::ChunkMut;
+    #[cfg(feature = "std")]
+    use crate::reader_mut::PNGReaderMut;
+    #[cfg(feature = "std")]
+    use crate::PNGReader;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn edit_text_in_place() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+        let mut new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("Title", "Ferris")
+            .unwrap()
+            .build()
+            .expect("Could not build PNG file");
+
+        let mut png_mut = PNGReaderMut::new(&mut new_png_file).expect("Could not validate PNG.");
+        for mut chunk in png_mut.iter_mut() {
+            if chunk.get_chunk_type() == "tEXt" {
+                chunk.get_chunk_data_mut()[6..].copy_from_slice(b"FERRIS");
+            }
+            if chunk.get_chunk_type() == "eXIf" {
+                // Clear the safe-to-copy bit
+                chunk.set_chunk_type("eXIF").unwrap();
+            }
+        }
+        assert_eq!(png_mut.as_reader().standard_text_fields().title.as_deref(), Some("FERRIS"));
+
+        let png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+        assert_eq!(png.standard_text_fields().title.as_deref(), Some("FERRIS"));
+        assert!(png.get_chunk_of_type("eXIF").is_some());
+        assert_eq!(png.chunk_count(), 10);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn explicit_commit() {
+        let mut png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let mut png_mut = PNGReaderMut::new(&mut png_file).expect("Could not validate PNG.");
+
+        let mut chunks: Vec<_> = png_mut.iter_mut().collect();
+        let phys = chunks.iter_mut().find(|chunk| chunk.get_chunk_type() == "pHYs").unwrap();
+        let old_crc = phys.get_crc();
+        phys.get_chunk_data_mut()[3] = 0;
+        phys.commit();
+        assert_ne!(phys.get_crc(), old_crc);
+        drop(chunks);
+
+        PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+    }
+
+    #[test]
+    fn drop_only_commits_edits() {
+        let chunk = PNGChunk::new("tEXt", b"Title\0Ferris").unwrap();
+        let crc = chunk.as_chunk_refs().get_crc();
+        let mut buffer = chunk.as_slice().to_vec();
+        let crc_start = buffer.len() - 4;
+        buffer[crc_start..].copy_from_slice(&0u32.to_be_bytes());
+
+        // Reading the chunk leaves the stored crc alone, even though it is wrong.
+        let chunk_mut = ChunkMut { chunk: &mut buffer, dirty: false };
+        assert_eq!(chunk_mut.get_chunk_data(), b"Title\0Ferris");
+        drop(chunk_mut);
+        assert_eq!(buffer[crc_start..], 0u32.to_be_bytes());
+
+        let mut chunk_mut = ChunkMut { chunk: &mut buffer, dirty: false };
+        chunk_mut.get_chunk_data_mut();
+        drop(chunk_mut);
+        assert_eq!(buffer[crc_start..], crc.to_be_bytes());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::chunk::refs::ChunkRefs;
+use crate::chunk::ty::ancillary::itxt::ITXt;
+use crate::chunk::ty::ancillary::text::{decode_latin1, TEXt};
+use crate::chunk::ty::ancillary::ztxt::ZTXt;
+use crate::PNGReader;
+use crate::error::PngError;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::vec;
+
+/// The keywords registered by the PNG specification for textual chunks, mapped to their own fields.
+/// Any keyword that is not registered ends up in `other`, in the order it appears in the file.
+///
+/// If a registered keyword appears more than once, the first value is kept in the typed field and the
+/// rest are pushed to `other`, so that no text is lost.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StandardText {
+    /// Short (one line) title or caption for image
+    pub title: Option<String>,
+    /// Name of image's creator
+    pub author: Option<String>,
+    /// Description of image (possibly long)
+    pub description: Option<String>,
+    /// Copyright notice
+    pub copyright: Option<String>,
+    /// Time of original image creation
+    pub creation_time: Option<String>,
+    /// Software used to create the image
+    pub software: Option<String>,
+    /// Legal disclaimer
+    pub disclaimer: Option<String>,
+    /// Warning of nature of content
+    pub warning: Option<String>,
+    /// Device used to create the image
+    pub source: Option<String>,
+    /// Miscellaneous comment
+    pub comment: Option<String>,
+    /// Every keyword/text pair that did not map to one of the registered keywords above.
+    pub other: Vec<(String, String)>,
+}
+
+impl StandardText {
+    /// Returns the field for the given keyword, if the keyword is one of the registered keywords.
+    fn field_mut(&mut self, keyword: &str) -> Option<&mut Option<String>> {
+        match keyword {
+            "Title" => Some(&mut self.title),
+            "Author" => Some(&mut self.author),
+            "Description" => Some(&mut self.description),
+            "Copyright" => Some(&mut self.copyright),
+            "Creation Time" => Some(&mut self.creation_time),
+            "Software" => Some(&mut self.software),
+            "Disclaimer" => Some(&mut self.disclaimer),
+            "Warning" => Some(&mut self.warning),
+            "Source" => Some(&mut self.source),
+            "Comment" => Some(&mut self.comment),
+            _ => None,
+        }
+    }
+    fn insert(&mut self, keyword: String, text: String) {
+        match self.field_mut(&keyword) {
+            Some(field @ None) => *field = Some(text),
+            _ => self.other.push((keyword, text)),
+        }
+    }
+}
+
+impl PNGReader<'_> {
+    /// Collects the tEXt, zTXt and iTXt chunks in the file into a `StandardText`.
+    ///
+    /// Compressed text (zTXt, and iTXt with the compression flag set) is only decoded when the
+    /// `text-decode` feature is enabled. Without it, compressed text chunks are skipped, as are any
+    /// text chunks that are malformed.
+    pub fn standard_text_fields(&self) -> StandardText {
+        let mut text = StandardText::default();
+
+        for chunk in self {
+            if let Some((keyword, value)) = decode_text_chunk(&chunk) {
+                text.insert(keyword, value);
+            }
+        }
+
+        text
+    }
+    /// Collects the keyword and text of every tEXt, zTXt and iTXt chunk in the file, in file order.
+    /// Keywords can appear more than once, so every pair is kept.
+    ///
+    /// Compressed text is only decoded when the `text-decode` feature is enabled. Without it, compressed
+    /// text chunks are skipped. Fails if any text chunk is malformed.
+    pub fn text_metadata(&self) -> Result<Vec<(String, String)>, PngError> {
+        let mut metadata = vec![];
+        for chunk in self {
+            if let Some(pair) = read_text_chunk(&chunk)? {
+                metadata.push(pair);
+            }
+        }
+
+        Ok(metadata)
+    }
+}
+
+/// Decodes the keyword and text of a tEXt, zTXt or iTXt chunk. Returns `None` for any other chunk type,
+/// or if the chunk could not be decoded.
+fn decode_text_chunk(chunk: &ChunkRefs<'_>) -> Option<(String, String)> {
+    read_text_chunk(chunk).ok().flatten()
+}
+/// Decodes the keyword and text of a tEXt, zTXt or iTXt chunk. Returns `None` for any other chunk type,
+/// and for compressed text when the `text-decode` feature is off. Fails if the chunk is malformed.
+fn read_text_chunk(chunk: &ChunkRefs<'_>) -> Result<Option<(String, String)>, PngError> {
+    let malformed = || PngError::InvalidData(
+        format!("Malformed {} chunk", chunk.get_chunk_type()),
+    );
+
+    match chunk.get_chunk_type() {
+        "tEXt" => {
+            let text = TEXt::from_chunk_refs(chunk).ok_or_else(malformed)?;
+            Ok(Some((text.get_keyword()?.into_owned(), text.get_text_latin1().into_owned())))
+        }
+        "zTXt" => {
+            let text = ZTXt::from_chunk_refs(chunk).ok_or_else(malformed)?;
+            text.validate()?;
+            let Some(decompressed) = inflate(text.get_compressed_data())? else {
+                return Ok(None);
+            };
+            Ok(Some((text.get_keyword()?.into_owned(), decode_latin1(&decompressed).into_owned())))
+        }
+        "iTXt" => {
+            let text = ITXt::from_chunk_refs(chunk).ok_or_else(malformed)?;
+            if text.is_compressed() && cfg!(not(feature = "text-decode")) {
+                return Ok(None);
+            }
+            Ok(Some((text.get_keyword()?.into_owned(), text.get_text()?)))
+        }
+        _ => Ok(None),
+    }
+}
+/// Decompresses zTXt text. Returns `None` without the `text-decode` feature.
+#[cfg(feature = "text-decode")]
+fn inflate(compressed: &[u8]) -> Result<Option<Vec<u8>>, PngError> {
+    crate::chunk::ty::ancillary::ztxt::inflate(compressed).map(Some)
+}
+#[cfg(not(feature = "text-decode"))]
+fn inflate(_compressed: &[u8]) -> Result<Option<Vec<u8>>, PngError> {
+    Ok(None)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::builder::PNGBuilder;
+    use crate::chunk::PNGChunk;
+    use crate::PNGReader;
+
+    #[test]
+    fn standard_text_fields() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_chunk(PNGChunk::new("tEXt", b"Title\0Ferris").unwrap())
+            .with_chunk(PNGChunk::new("tEXt", b"Author\0Caf\xE9").unwrap())
+            .with_chunk(PNGChunk::new("tEXt", b"Title\0Second title").unwrap())
+            .with_chunk(PNGChunk::new("iTXt", "Software\0\0\0en\0\0png-util ✓".as_bytes()).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        let text = new_png.standard_text_fields();
+
+        assert_eq!(text.title.as_deref(), Some("Ferris"));
+        assert_eq!(text.author.as_deref(), Some("Café"));
+        assert_eq!(text.software.as_deref(), Some("png-util ✓"));
+        assert_eq!(text.comment, None);
+        // ferris.png carries its XMP metadata in an iTXt chunk.
+        assert_eq!(text.other[0].0, "XML:com.adobe.xmp");
+        assert_eq!(text.other[1], ("Title".to_string(), "Second title".to_string()));
+    }
+
+    #[test]
+    fn text_metadata() {
+        let png_file = std::fs::read("ferris.png").expect("Could not read png file");
+        let png = PNGReader::new(&png_file[..]).expect("Could not validate PNG.");
+
+        let new_png_file = PNGBuilder::new()
+            .with_png(&png)
+            .with_text("Title", "Ferris").unwrap()
+            .with_text("Comment", "crab").unwrap()
+            .with_text("Comment", "also a crab").unwrap()
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        let metadata = new_png.text_metadata().unwrap();
+        let keywords: Vec<&str> = metadata.iter().map(|(keyword, _)| keyword.as_str()).collect();
+        // ferris.png carries its XMP metadata in an iTXt chunk.
+        assert_eq!(keywords, ["XML:com.adobe.xmp", "Title", "Comment", "Comment"]);
+        assert_eq!(metadata[3].1, "also a crab");
+
+        let malformed = PNGBuilder::new()
+            .with_png(&png)
+            .with_chunk(PNGChunk::new("tEXt", b"No separator").unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let malformed = PNGReader::new(&malformed[..]).expect("Could not validate PNG.");
+        assert!(malformed.text_metadata().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "text-decode")]
+    fn standard_text_fields_compressed() {
+        use flate2::write::ZlibEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = ZlibEncoder::new(b"Comment\0\0".to_vec(), Compression::default());
+        encoder.write_all(b"A compressed comment").unwrap();
+        let ztxt_data = encoder.finish().unwrap();
+
+        let new_png_file = PNGBuilder::new()
+            .with_chunk(PNGChunk::new("IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]).unwrap())
+            .with_chunk(PNGChunk::new("zTXt", &ztxt_data).unwrap())
+            .build()
+            .expect("Could not build PNG file");
+        let new_png = PNGReader::new(&new_png_file[..]).expect("Could not validate PNG.");
+
+        let text = new_png.standard_text_fields();
+
+        assert_eq!(text.comment.as_deref(), Some("A compressed comment"));
+        assert_eq!(
+            new_png.text_metadata().unwrap(),
+            [("Comment".to_string(), "A compressed comment".to_string())]
+        );
+    }
+}
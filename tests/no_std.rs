@@ -0,0 +1,53 @@
+//! Uses the reader, iterator, crc and chunk types from a `no_std` crate, with only `alloc`. Run it without
+//! the `std` feature to check that the core of the crate builds under `no_std`:
+//!
+//! `cargo test --no-default-features --test no_std`
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use png_util::chunk::refs::ChunkRefs;
+use png_util::chunk::ty::ancillary::phys::PHYS;
+use png_util::chunk::PNGChunk;
+use png_util::error::PngError;
+use png_util::PNGReader;
+
+static FERRIS: &[u8] = include_bytes!("../ferris.png");
+
+#[test]
+fn read_chunks() {
+    let png = PNGReader::new(FERRIS).expect("Could not validate PNG.");
+
+    let chunk_types: Vec<&str> = (&png).into_iter().map(|chunk| chunk.get_chunk_type()).collect();
+    assert_eq!(chunk_types, ["IHDR", "sRGB", "eXIf", "pHYs", "iTXt", "IDAT", "IDAT", "IDAT", "IEND"]);
+    assert_eq!(png.dimensions().unwrap(), (460, 307));
+    png.is_minimal_compliant().unwrap();
+    png.validate_iend().unwrap();
+
+    let phys = png.get_chunk_of_type("pHYs").unwrap();
+    PHYS::from_chunk_refs(&phys).unwrap().validate().unwrap();
+}
+
+#[test]
+fn errors() {
+    let truncated = PNGReader::new(&FERRIS[..FERRIS.len() - 1]);
+    assert!(matches!(truncated, Err(PngError::TruncatedBuffer { .. })));
+
+    let mut corrupted = FERRIS.to_vec();
+    // The last byte of the IHDR crc.
+    corrupted[32] ^= 0xFF;
+    let err = PNGReader::new(&corrupted).err().unwrap();
+    assert!(matches!(err, PngError::CrcMismatch { index: 0, .. }));
+}
+
+#[test]
+fn build_chunk() {
+    let chunk = PNGChunk::new("tEXt", b"Title\0Ferris").unwrap();
+    assert!(chunk.as_chunk_refs().validate_crc());
+
+    let refs = ChunkRefs::from_raw(chunk.as_slice()).unwrap();
+    assert_eq!(refs.get_chunk_data(), b"Title\0Ferris");
+    assert!(ChunkRefs::from_raw(&chunk.as_slice()[..7]).is_err());
+    assert!(PNGChunk::new("tE1t", &[]).is_err());
+}